@@ -169,7 +169,7 @@ impl Bytes {
 impl<T: Int> Bytes<T> {
     /// return inner value of Bytes
     pub fn size(&self) -> T {
-        return self.0;
+        self.0
     }
 }
 
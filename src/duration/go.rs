@@ -0,0 +1,295 @@
+//! This module parses and formats durations exactly like Go's
+//! [`time.ParseDuration`] and [`Duration.String`].
+//!
+//! Go durations are signed 64-bit nanosecond counts, so they are represented as `i64` here.
+//!
+//! # Example
+//! ```
+//! use humanize_rs::duration::go::{format, parse};
+//!
+//! assert_eq!(parse("1h15m30.918273645s"), Ok(4530918273645));
+//! assert_eq!(parse("-1.5h"), Ok(-5_400_000_000_000));
+//! assert_eq!(format(4530918273645), "1h15m30.918273645s");
+//! assert_eq!(format(300_000_000), "300ms");
+//! ```
+//!
+//! [`time.ParseDuration`]: https://golang.org/pkg/time/#ParseDuration
+//! [`Duration.String`]: https://golang.org/pkg/time/#Duration.String
+
+use ParseError;
+
+const NANOSECOND: u64 = 1;
+const MICROSECOND: u64 = 1000 * NANOSECOND;
+const MILLISECOND: u64 = 1000 * MICROSECOND;
+const SECOND: u64 = 1000 * MILLISECOND;
+const MINUTE: u64 = 60 * SECOND;
+const HOUR: u64 = 60 * MINUTE;
+
+const MAX: u64 = 1 << 63;
+
+/// Parses a Go duration string, (e.g. "300ms", "-1.5h", "2h45m"),
+/// and returns the duration in nanoseconds.
+///
+/// Valid units are "ns", "us" (or "µs"), "ms", "s", "m", "h".
+pub fn parse(s: &str) -> Result<i64, ParseError> {
+    let mut bs = s.as_bytes();
+    if bs.is_empty() {
+        return Err(ParseError::EmptyInput);
+    }
+
+    let mut neg = false;
+    if bs[0] == b'-' || bs[0] == b'+' {
+        neg = bs[0] == b'-';
+        bs = &bs[1..];
+    }
+
+    if bs == b"0" {
+        return Ok(0);
+    }
+
+    if bs.is_empty() {
+        return Err(ParseError::MissingValue);
+    }
+
+    let mut d: u64 = 0;
+    while !bs.is_empty() {
+        // the next character must be [0-9.]
+        if !(bs[0] == b'.' || bs[0].is_ascii_digit()) {
+            return Err(ParseError::MissingValue);
+        }
+
+        let (mut v, read) = leading_int(bs)?;
+        bs = &bs[read..];
+        let pre = read != 0;
+
+        let mut f: u64 = 0;
+        let mut scale: f64 = 1.0;
+        let mut post = false;
+        if !bs.is_empty() && bs[0] == b'.' {
+            let (x, sc, read) = leading_fraction(&bs[1..]);
+            f = x;
+            scale = sc;
+            post = read != 0;
+            bs = &bs[1 + read..];
+        }
+
+        // no digits, (e.g. ".s", "-.s")
+        if !pre && !post {
+            return Err(ParseError::MissingValue);
+        }
+
+        let i = bs
+            .iter()
+            .position(|&c| c == b'.' || c.is_ascii_digit())
+            .unwrap_or(bs.len());
+        if i == 0 {
+            return Err(ParseError::MissingUnit);
+        }
+
+        let unit = unit_to_nanos(&bs[..i])?;
+        bs = &bs[i..];
+
+        if v > MAX / unit {
+            return Err(ParseError::Overflow);
+        }
+
+        v *= unit;
+        if f > 0 {
+            // f64 is needed to be nanosecond accurate for fractions of hours,
+            // f * unit / scale never exceeds 3.6e12
+            v += (f as f64 * (unit as f64 / scale)) as u64;
+            if v > MAX {
+                return Err(ParseError::Overflow);
+            }
+        }
+
+        d = d.checked_add(v).ok_or(ParseError::Overflow)?;
+        if d > MAX {
+            return Err(ParseError::Overflow);
+        }
+    }
+
+    if neg {
+        return Ok((d as i64).wrapping_neg());
+    }
+
+    if d > MAX - 1 {
+        return Err(ParseError::Overflow);
+    }
+
+    Ok(d as i64)
+}
+
+/// Formats a duration in nanoseconds the same way as Go's `Duration.String`,
+/// (e.g. "72h3m0.5s", "1.2ms", "0s")
+pub fn format(nanos: i64) -> String {
+    // largest time is "2562047h47m16.854775808s"
+    let mut buf = [0u8; 32];
+    let mut w = buf.len();
+
+    let neg = nanos < 0;
+    let mut u = nanos.wrapping_abs() as u64;
+
+    if u < SECOND {
+        // use smaller units for durations shorter than one second, like "1.2ms"
+        let prec: usize;
+        w -= 1;
+        buf[w] = b's';
+        w -= 1;
+        if u == 0 {
+            buf[w] = b'0';
+            return String::from_utf8_lossy(&buf[w..]).into_owned();
+        } else if u < MICROSECOND {
+            prec = 0;
+            buf[w] = b'n';
+        } else if u < MILLISECOND {
+            prec = 3;
+            // 'µ' is encoded as 0xC2 0xB5
+            w -= 1;
+            buf[w..w + 2].copy_from_slice("\u{b5}".as_bytes());
+        } else {
+            prec = 6;
+            buf[w] = b'm';
+        }
+
+        let (nw, nv) = format_frac(&mut buf[..w], u, prec);
+        w = format_int(&mut buf[..nw], nv);
+    } else {
+        w -= 1;
+        buf[w] = b's';
+
+        let (nw, nv) = format_frac(&mut buf[..w], u, 9);
+        u = nv;
+
+        // u is now integer seconds
+        w = format_int(&mut buf[..nw], u % 60);
+        u /= 60;
+
+        // u is now integer minutes
+        if u > 0 {
+            w -= 1;
+            buf[w] = b'm';
+            w = format_int(&mut buf[..w], u % 60);
+            u /= 60;
+
+            // u is now integer hours, stop at hours because days can be different lengths
+            if u > 0 {
+                w -= 1;
+                buf[w] = b'h';
+                w = format_int(&mut buf[..w], u);
+            }
+        }
+    }
+
+    if neg {
+        w -= 1;
+        buf[w] = b'-';
+    }
+
+    String::from_utf8_lossy(&buf[w..]).into_owned()
+}
+
+fn unit_to_nanos(unit: &[u8]) -> Result<u64, ParseError> {
+    match unit {
+        b"ns" => Ok(NANOSECOND),
+        b"us" => Ok(MICROSECOND),
+        b"\xc2\xb5s" => Ok(MICROSECOND), // U+00B5 MICRO SIGN
+        b"\xce\xbcs" => Ok(MICROSECOND), // U+03BC GREEK SMALL LETTER MU
+        b"ms" => Ok(MILLISECOND),
+        b"s" => Ok(SECOND),
+        b"m" => Ok(MINUTE),
+        b"h" => Ok(HOUR),
+        _ => Err(ParseError::InvalidUnit),
+    }
+}
+
+// consumes the leading [0-9]*
+fn leading_int(bs: &[u8]) -> Result<(u64, usize), ParseError> {
+    let mut x: u64 = 0;
+    let mut read: usize = 0;
+    while read < bs.len() && bs[read].is_ascii_digit() {
+        if x > MAX / 10 {
+            return Err(ParseError::Overflow);
+        }
+
+        x = x * 10 + u64::from(bs[read] - b'0');
+        if x > MAX {
+            return Err(ParseError::Overflow);
+        }
+
+        read += 1;
+    }
+
+    Ok((x, read))
+}
+
+// consumes the leading [0-9]* of a fraction, digits that would overflow only lose precision
+fn leading_fraction(bs: &[u8]) -> (u64, f64, usize) {
+    let mut x: u64 = 0;
+    let mut scale: f64 = 1.0;
+    let mut overflow = false;
+    let mut read: usize = 0;
+    while read < bs.len() && bs[read].is_ascii_digit() {
+        read += 1;
+        if overflow {
+            continue;
+        }
+
+        if x > (MAX - 1) / 10 {
+            overflow = true;
+            continue;
+        }
+
+        let y = x * 10 + u64::from(bs[read - 1] - b'0');
+        if y > MAX {
+            overflow = true;
+            continue;
+        }
+
+        x = y;
+        scale *= 10.0;
+    }
+
+    (x, scale, read)
+}
+
+// writes the fraction of v / 10^prec into the tail of buf, omitting trailing zeros,
+// returns the index where the output begins and v / 10^prec
+fn format_frac(buf: &mut [u8], mut v: u64, prec: usize) -> (usize, u64) {
+    let mut w = buf.len();
+    let mut print = false;
+    for _ in 0..prec {
+        let digit = v % 10;
+        print = print || digit != 0;
+        if print {
+            w -= 1;
+            buf[w] = digit as u8 + b'0';
+        }
+        v /= 10;
+    }
+
+    if print {
+        w -= 1;
+        buf[w] = b'.';
+    }
+
+    (w, v)
+}
+
+// writes v into the tail of buf, returns the index where the output begins
+fn format_int(buf: &mut [u8], mut v: u64) -> usize {
+    let mut w = buf.len();
+    if v == 0 {
+        w -= 1;
+        buf[w] = b'0';
+        return w;
+    }
+
+    while v > 0 {
+        w -= 1;
+        buf[w] = (v % 10) as u8 + b'0';
+        v /= 10;
+    }
+
+    w
+}
@@ -0,0 +1,200 @@
+//! This module parses and formats [`ISO 8601`] durations, (e.g. "P1DT2H30M", "PT0.5S", "P2W")
+//!
+//! Only the exact components are supported: weeks, days, hours, minutes and seconds,
+//! a day is always 24 hours. Years and months vary in length, so they are rejected.
+//! The last component may have a fraction, with "." or "," as the decimal sign.
+//!
+//! # Example
+//! ```
+//! use humanize_rs::duration::iso8601::{format, parse};
+//! use std::time::Duration;
+//!
+//! assert_eq!(parse("P1DT2H30M"), Ok(Duration::from_secs(95400)));
+//! assert_eq!(parse("PT0.5S"), Ok(Duration::from_millis(500)));
+//! assert_eq!(parse("P2W"), Ok(Duration::from_secs(14 * 86400)));
+//! assert_eq!(format(Duration::from_secs(95400)), "P1DT2H30M");
+//! assert_eq!(format(Duration::from_millis(1500)), "PT1.5S");
+//! ```
+//!
+//! [`ISO 8601`]: https://en.wikipedia.org/wiki/ISO_8601#Durations
+
+use std::fmt::Write;
+use std::time::Duration;
+use ParseError;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+const SECS_PER_MINUTE: u64 = 60;
+const SECS_PER_HOUR: u64 = 60 * SECS_PER_MINUTE;
+const SECS_PER_DAY: u64 = 24 * SECS_PER_HOUR;
+const SECS_PER_WEEK: u64 = 7 * SECS_PER_DAY;
+
+// (designator, seconds, in the time part) of each component, in the required order
+const COMPONENTS: [(u8, u64, bool); 5] = [
+    (b'W', SECS_PER_WEEK, false),
+    (b'D', SECS_PER_DAY, false),
+    (b'H', SECS_PER_HOUR, true),
+    (b'M', SECS_PER_MINUTE, true),
+    (b'S', 1, true),
+];
+
+/// Parses an ISO 8601 duration, (e.g. "P1DT2H30M", "PT0.5S", "P2W")
+///
+/// Returns `ParseError::InvalidUnit` for the years and months of "P1Y2M",
+/// as they have no fixed length.
+pub fn parse(s: &str) -> Result<Duration, ParseError> {
+    let bs = s.trim().as_bytes();
+    if bs.is_empty() {
+        return Err(ParseError::EmptyInput);
+    }
+
+    if bs[0] != b'P' {
+        return Err(ParseError::Malformed);
+    }
+
+    let mut bs = &bs[1..];
+    let mut in_time = false;
+    // index into COMPONENTS of the next allowed component
+    let mut next = 0;
+    let mut d = Duration::new(0, 0);
+    let mut components = 0;
+
+    while !bs.is_empty() {
+        if bs[0] == b'T' {
+            if in_time {
+                return Err(ParseError::Malformed);
+            }
+            in_time = true;
+            bs = &bs[1..];
+            if bs.is_empty() {
+                return Err(ParseError::MissingValue);
+            }
+            continue;
+        }
+
+        let digits = bs.iter().take_while(|c| c.is_ascii_digit()).count();
+        let (int, rest) = bs.split_at(digits);
+        bs = rest;
+
+        let mut fraction: &[u8] = &[];
+        if !bs.is_empty() && (bs[0] == b'.' || bs[0] == b',') {
+            let n = bs[1..].iter().take_while(|c| c.is_ascii_digit()).count();
+            if n == 0 {
+                return Err(ParseError::MissingValue);
+            }
+            fraction = &bs[1..=n];
+            bs = &bs[1 + n..];
+        }
+
+        if int.is_empty() {
+            return Err(ParseError::MissingValue);
+        }
+
+        let designator = match bs.first() {
+            Some(&c) => c,
+            None => return Err(ParseError::MissingUnit),
+        };
+        bs = &bs[1..];
+
+        let i = match COMPONENTS
+            .iter()
+            .position(|&(c, _, time)| c == designator && time == in_time)
+        {
+            Some(i) => i,
+            // years and months
+            None if !in_time && (designator == b'Y' || designator == b'M') => {
+                return Err(ParseError::InvalidUnit)
+            }
+            None if designator.is_ascii_alphabetic() => return Err(ParseError::InvalidUnit),
+            None => return Err(ParseError::Malformed),
+        };
+
+        if i + 1 == next {
+            return Err(ParseError::DuplicateUnit);
+        }
+        if i < next {
+            return Err(ParseError::Malformed);
+        }
+        next = i + 1;
+
+        // only the last component may have a fraction
+        if !fraction.is_empty() && !bs.is_empty() {
+            return Err(ParseError::Malformed);
+        }
+
+        let secs = COMPONENTS[i].1;
+        let part = component(int, fraction, secs).ok_or(ParseError::Overflow)?;
+        d = d.checked_add(part).ok_or(ParseError::Overflow)?;
+        components += 1;
+    }
+
+    if components == 0 {
+        return Err(ParseError::MissingValue);
+    }
+
+    Ok(d)
+}
+
+/// Formats the duration as an ISO 8601 duration of days, hours, minutes and seconds,
+/// (e.g. "P1DT2H30M", "PT1.5S"), a zero duration is "PT0S"
+pub fn format(d: Duration) -> String {
+    let secs = d.as_secs();
+    let nanos = d.subsec_nanos();
+    if secs == 0 && nanos == 0 {
+        return "PT0S".to_owned();
+    }
+
+    let mut s = String::from("P");
+    let days = secs / SECS_PER_DAY;
+    if days > 0 {
+        let _ = write!(s, "{}D", days);
+    }
+
+    let hours = secs % SECS_PER_DAY / SECS_PER_HOUR;
+    let minutes = secs % SECS_PER_HOUR / SECS_PER_MINUTE;
+    let seconds = secs % SECS_PER_MINUTE;
+    if hours == 0 && minutes == 0 && seconds == 0 && nanos == 0 {
+        return s;
+    }
+
+    s.push('T');
+    if hours > 0 {
+        let _ = write!(s, "{}H", hours);
+    }
+    if minutes > 0 {
+        let _ = write!(s, "{}M", minutes);
+    }
+    if seconds > 0 || nanos > 0 {
+        let _ = write!(s, "{}", seconds);
+        if nanos > 0 {
+            let fraction = format!("{:09}", nanos);
+            let _ = write!(s, ".{}", fraction.trim_end_matches('0'));
+        }
+        s.push('S');
+    }
+
+    s
+}
+
+// returns the duration of `int.fraction` components of `secs` seconds each,
+// digits of the fraction beyond nanoseconds are dropped
+fn component(int: &[u8], fraction: &[u8], secs: u64) -> Option<Duration> {
+    let int = digits(int)?;
+    let d = Duration::from_secs(int.checked_mul(secs)?);
+    if fraction.is_empty() {
+        return Some(d);
+    }
+
+    let fraction = &fraction[..fraction.len().min(9)];
+    let scale = 10u64.pow(fraction.len() as u32);
+    // less than 10^9 times a week in seconds, which fits in u64
+    let total = digits(fraction)? * secs;
+    let nanos = total % scale * (NANOS_PER_SEC / scale);
+
+    d.checked_add(Duration::new(total / scale, nanos as u32))
+}
+
+fn digits(bs: &[u8]) -> Option<u64> {
+    bs.iter().try_fold(0u64, |acc, &c| {
+        acc.checked_mul(10)?.checked_add(u64::from(c - b'0'))
+    })
+}
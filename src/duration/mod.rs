@@ -8,23 +8,129 @@
 //! assert_eq!(parse("1h 30m 71s"), Ok(Duration::from_secs(60 * 90 + 71)));
 //! ```
 
-use std::str::from_utf8;
+pub mod go;
+pub mod iso8601;
+pub mod systemd;
+
+mod phrase;
+
+pub use self::phrase::*;
+
+use std::fmt;
+use std::str::{from_utf8, FromStr};
 use std::time::Duration;
 use ParseError;
 
 const DIGIT_MIN: u8 = b'0';
 const DIGIT_MAX: u8 = b'9';
 
-const NANOS: [u64; 7] = [
-    1,                         // ns
-    1_000,                     // us
-    1_000_000,                 // ms
-    1_000_000_000,             // s
-    60 * 1_000_000_000,        // min
-    3600 * 1_000_000_000,      // h
-    24 * 3600 * 1_000_000_000, // d
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+// (seconds, nanoseconds) of each unit, a unit is either a whole number of
+// seconds or a fraction of one second
+const UNITS: [(u64, u64); 7] = [
+    (0, 1),         // ns
+    (0, 1_000),     // us
+    (0, 1_000_000), // ms
+    (1, 0),         // s
+    (60, 0),        // min
+    (3600, 0),      // h
+    (24 * 3600, 0), // d
+];
+
+const ALL_UNITS: [Unit; 7] = [
+    Unit::Nanosecond,
+    Unit::Microsecond,
+    Unit::Millisecond,
+    Unit::Second,
+    Unit::Minute,
+    Unit::Hour,
+    Unit::Day,
 ];
 
+/// Duration units, like "ms", "h"
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Unit {
+    /// 1 nanosecond
+    Nanosecond,
+
+    /// 1000 nanoseconds
+    Microsecond,
+
+    /// 1000 microseconds
+    Millisecond,
+
+    /// 1000 milliseconds
+    Second,
+
+    /// 60 seconds
+    Minute,
+
+    /// 60 minutes
+    Hour,
+
+    /// 24 hours
+    Day,
+}
+
+impl Unit {
+    /// Returns the length of the unit
+    pub fn duration(&self) -> Duration {
+        let (secs, nanos) = UNITS[*self as usize];
+        Duration::new(secs, nanos as u32)
+    }
+
+    // returns the length of `v` units, or None if it overflows
+    pub(crate) fn times(&self, v: u64) -> Option<Duration> {
+        self.split(v)
+            .map(|(secs, nanos)| Duration::new(secs, nanos as u32))
+    }
+
+    // splits `v` units into whole seconds and the remaining nanoseconds
+    fn split(&self, v: u64) -> Option<(u64, u64)> {
+        let (secs, nanos) = UNITS[*self as usize];
+        if secs == 0 {
+            let per_sec = NANOS_PER_SEC / nanos;
+            return Some((v / per_sec, v % per_sec * nanos));
+        }
+
+        v.checked_mul(secs).map(|secs| (secs, 0))
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let unit = match self {
+            Unit::Nanosecond => "ns",
+            Unit::Microsecond => "us",
+            Unit::Millisecond => "ms",
+            Unit::Second => "s",
+            Unit::Minute => "m",
+            Unit::Hour => "h",
+            Unit::Day => "d",
+        };
+
+        f.pad(unit)
+    }
+}
+
+impl FromStr for Unit {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ns" => Ok(Unit::Nanosecond),
+            "us" => Ok(Unit::Microsecond),
+            "ms" => Ok(Unit::Millisecond),
+            "s" => Ok(Unit::Second),
+            "m" => Ok(Unit::Minute),
+            "h" => Ok(Unit::Hour),
+            "d" => Ok(Unit::Day),
+            _ => Err(ParseError::InvalidUnit),
+        }
+    }
+}
+
 /// parse a duration-type string, (e.g. "1h", "1h 30m")
 ///
 /// # Example
@@ -45,7 +151,8 @@ pub fn parse(s: &str) -> Result<Duration, ParseError> {
         return Ok(Duration::new(0, 0));
     }
 
-    let mut value: u64 = 0;
+    let mut secs: u64 = 0;
+    let mut nanos: u64 = 0;
 
     let bs = input.as_bytes();
     let mut read: usize = 0;
@@ -57,15 +164,17 @@ pub fn parse(s: &str) -> Result<Duration, ParseError> {
         let (unit, consumed) = read_unit(&bs[read..])?;
         read += consumed;
 
-        let nanos = unit_to_nanos(unit)?;
+        let (s, n) = unit.parse::<Unit>()?.split(v).ok_or(ParseError::Overflow)?;
 
-        value = v
-            .checked_mul(nanos)
-            .and_then(|res| value.checked_add(res))
+        nanos += n;
+        secs = secs
+            .checked_add(s)
+            .and_then(|res| res.checked_add(nanos / NANOS_PER_SEC))
             .ok_or(ParseError::Overflow)?;
+        nanos %= NANOS_PER_SEC;
     }
 
-    Ok(Duration::from_nanos(value))
+    Ok(Duration::new(secs, nanos as u32))
 }
 
 fn read_int(bs: &[u8]) -> Result<(u64, usize), ParseError> {
@@ -73,7 +182,7 @@ fn read_int(bs: &[u8]) -> Result<(u64, usize), ParseError> {
     let mut read: usize = 0;
     while read < bs.len() {
         let c = bs[read];
-        if c < DIGIT_MIN || c > DIGIT_MAX {
+        if !(DIGIT_MIN..=DIGIT_MAX).contains(&c) {
             break;
         }
 
@@ -96,7 +205,7 @@ fn read_unit(bs: &[u8]) -> Result<(&str, usize), ParseError> {
     let mut read: usize = 0;
     while read < bs.len() {
         let c = bs[read];
-        if DIGIT_MIN <= c && c <= DIGIT_MAX {
+        if (DIGIT_MIN..=DIGIT_MAX).contains(&c) {
             break;
         }
 
@@ -112,17 +221,54 @@ fn read_unit(bs: &[u8]) -> Result<(&str, usize), ParseError> {
     Ok((unit.trim(), read))
 }
 
-fn unit_to_nanos(unit: &str) -> Result<u64, ParseError> {
-    match unit {
-        "ns" => Ok(NANOS[0]),
-        "us" => Ok(NANOS[1]),
-        "ms" => Ok(NANOS[2]),
-        "s" => Ok(NANOS[3]),
-        "m" => Ok(NANOS[4]),
-        "h" => Ok(NANOS[5]),
-        "d" => Ok(NANOS[6]),
-        _ => Err(ParseError::InvalidUnit),
+/// Returns the result of rounding `d` toward zero to a multiple of `unit`
+///
+/// # Example
+/// ```
+/// use humanize_rs::duration::{truncate, Unit};
+/// use std::time::Duration;
+///
+/// assert_eq!(
+///     truncate(Duration::from_millis(1999), Unit::Second),
+///     Duration::from_secs(1)
+/// );
+/// ```
+pub fn truncate(d: Duration, unit: Unit) -> Duration {
+    let (secs, nanos) = UNITS[unit as usize];
+    if secs == 0 {
+        let subsec = d.subsec_nanos() as u64;
+        return Duration::new(d.as_secs(), (subsec - subsec % nanos) as u32);
+    }
+
+    Duration::from_secs(d.as_secs() - d.as_secs() % secs)
+}
+
+/// Returns the result of rounding `d` to the nearest multiple of `unit`,
+/// halfway values are rounded up, returns None if the result overflows.
+///
+/// # Example
+/// ```
+/// use humanize_rs::duration::{round, Unit};
+/// use std::time::Duration;
+///
+/// assert_eq!(
+///     round(Duration::from_millis(1500), Unit::Second),
+///     Some(Duration::from_secs(2))
+/// );
+/// ```
+pub fn round(d: Duration, unit: Unit) -> Option<Duration> {
+    let (secs, nanos) = UNITS[unit as usize];
+    let truncated = truncate(d, unit);
+
+    // both remainder and unit are less than 1 day, which fits in u64 nanoseconds
+    let rem = d - truncated;
+    let rem_nanos = rem.as_secs() * NANOS_PER_SEC + rem.subsec_nanos() as u64;
+    let unit_nanos = secs * NANOS_PER_SEC + nanos;
+    if rem_nanos * 2 < unit_nanos {
+        return Some(truncated);
     }
+
+    truncated.checked_add(unit.duration())
 }
 
 #[cfg(test)]
@@ -0,0 +1,223 @@
+use super::{round, truncate, Unit, ALL_UNITS};
+use std::time::Duration;
+
+/// Describes durations in a natural language
+pub trait Locale {
+    /// Returns the phrase of `count` units, (e.g. "1 hour", "2 hours")
+    fn units(&self, count: u64, unit: Unit) -> String;
+
+    /// Returns the phrase of a duration shorter than one unit, (e.g. "less than a second")
+    fn less_than(&self, unit: Unit) -> String;
+
+    /// Marks a phrase as approximate, (e.g. "about 3 days")
+    fn about(&self, phrase: &str) -> String;
+
+    /// Joins the phrases of the components, (e.g. "2 hours 5 minutes")
+    fn join(&self, parts: &[String]) -> String {
+        parts.join(" ")
+    }
+}
+
+/// The built-in English locale
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct English;
+
+impl English {
+    // returns the English name of the unit
+    pub(crate) fn unit_name(unit: Unit, plural: bool) -> &'static str {
+        match (unit, plural) {
+            (Unit::Nanosecond, false) => "nanosecond",
+            (Unit::Nanosecond, true) => "nanoseconds",
+            (Unit::Microsecond, false) => "microsecond",
+            (Unit::Microsecond, true) => "microseconds",
+            (Unit::Millisecond, false) => "millisecond",
+            (Unit::Millisecond, true) => "milliseconds",
+            (Unit::Second, false) => "second",
+            (Unit::Second, true) => "seconds",
+            (Unit::Minute, false) => "minute",
+            (Unit::Minute, true) => "minutes",
+            (Unit::Hour, false) => "hour",
+            (Unit::Hour, true) => "hours",
+            (Unit::Day, false) => "day",
+            (Unit::Day, true) => "days",
+        }
+    }
+
+    // returns the unit of the English name, in singular or plural
+    pub(crate) fn parse_unit_name(name: &str) -> Option<Unit> {
+        ALL_UNITS.iter().cloned().find(|&unit| {
+            English::unit_name(unit, false) == name || English::unit_name(unit, true) == name
+        })
+    }
+}
+
+impl Locale for English {
+    fn units(&self, count: u64, unit: Unit) -> String {
+        format!("{} {}", count, English::unit_name(unit, count != 1))
+    }
+
+    fn less_than(&self, unit: Unit) -> String {
+        let article = match unit {
+            Unit::Hour => "an",
+            _ => "a",
+        };
+
+        format!("less than {} {}", article, English::unit_name(unit, false))
+    }
+
+    fn about(&self, phrase: &str) -> String {
+        format!("about {}", phrase)
+    }
+}
+
+/// Formats durations as approximate, human-friendly phrases,
+/// keeping only the most significant components.
+///
+/// # Example
+/// ```
+/// use humanize_rs::duration::{Humanizer, Unit};
+/// use std::time::Duration;
+///
+/// let h = Humanizer::new();
+/// assert_eq!(h.format(Duration::from_secs(7500)), "2 hours 5 minutes");
+/// assert_eq!(h.format(Duration::from_secs(7510)), "about 2 hours 5 minutes");
+/// assert_eq!(h.format(Duration::from_millis(300)), "less than a second");
+///
+/// let h = Humanizer::new().components(1);
+/// assert_eq!(h.format(Duration::from_secs(3 * 86400 - 600)), "about 3 days");
+///
+/// let h = Humanizer::new().granularity(Unit::Millisecond);
+/// assert_eq!(h.format(Duration::from_millis(1300)), "1 second 300 milliseconds");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Humanizer<L: Locale = English> {
+    locale: L,
+    components: usize,
+    granularity: Unit,
+    approximate: bool,
+}
+
+impl Humanizer {
+    /// Returns an English humanizer which keeps 2 components, down to seconds
+    pub fn new() -> Humanizer {
+        Humanizer::with_locale(English)
+    }
+}
+
+impl Default for Humanizer {
+    fn default() -> Humanizer {
+        Humanizer::new()
+    }
+}
+
+impl<L: Locale> Humanizer<L> {
+    /// Returns a humanizer using the given locale, which keeps 2 components, down to seconds
+    pub fn with_locale(locale: L) -> Humanizer<L> {
+        Humanizer {
+            locale,
+            components: 2,
+            granularity: Unit::Second,
+            approximate: true,
+        }
+    }
+
+    /// Sets the max number of components in the phrase, at least 1
+    pub fn components(mut self, n: usize) -> Humanizer<L> {
+        self.components = if n == 0 { 1 } else { n };
+        self
+    }
+
+    /// Sets the smallest unit in the phrase
+    pub fn granularity(mut self, unit: Unit) -> Humanizer<L> {
+        self.granularity = unit;
+        self
+    }
+
+    /// Sets whether rounded phrases are marked as approximate, (e.g. "about 3 days")
+    pub fn approximate(mut self, approximate: bool) -> Humanizer<L> {
+        self.approximate = approximate;
+        self
+    }
+
+    /// Returns the phrase of the duration
+    pub fn format(&self, d: Duration) -> String {
+        if d == Duration::new(0, 0) {
+            return self.locale.units(0, self.granularity);
+        }
+
+        if d < self.granularity.duration() {
+            return self.locale.less_than(self.granularity);
+        }
+
+        let (_, last) = self.span(d);
+        let last = ALL_UNITS[last];
+        let rounded = round(d, last).unwrap_or_else(|| truncate(d, last));
+
+        // rounding up may carry into a larger unit, (e.g. 59.6s to 1m)
+        let (top, last) = self.span(rounded);
+
+        let mut rem = rounded;
+        let mut parts = Vec::new();
+        for i in (last..=top).rev() {
+            let unit = ALL_UNITS[i].duration();
+            let count = div(rem, unit);
+            if count > 0 {
+                rem -= mul(unit, count);
+                parts.push(self.locale.units(count, ALL_UNITS[i]));
+            }
+        }
+
+        let phrase = self.locale.join(&parts);
+        if self.approximate && rounded != d {
+            return self.locale.about(&phrase);
+        }
+
+        phrase
+    }
+
+    // returns the indexes of the most and the least significant units of the phrase
+    fn span(&self, d: Duration) -> (usize, usize) {
+        let min = self.granularity as usize;
+        let top = (min..ALL_UNITS.len())
+            .rev()
+            .find(|&i| d >= ALL_UNITS[i].duration())
+            .unwrap_or(min);
+
+        let last = if top - min + 1 > self.components {
+            top + 1 - self.components
+        } else {
+            min
+        };
+
+        (top, last)
+    }
+}
+
+/// Returns the approximate English phrase of the duration, (e.g. "about 3 days"),
+/// see [`Humanizer`] for more options.
+///
+/// [`Humanizer`]: ./struct.Humanizer.html
+pub fn humanize(d: Duration) -> String {
+    Humanizer::new().format(d)
+}
+
+fn div(d: Duration, unit: Duration) -> u64 {
+    if unit.as_secs() > 0 {
+        return d.as_secs() / unit.as_secs();
+    }
+
+    let per_sec = 1_000_000_000 / u64::from(unit.subsec_nanos());
+    d.as_secs() * per_sec + u64::from(d.subsec_nanos() / unit.subsec_nanos())
+}
+
+fn mul(unit: Duration, count: u64) -> Duration {
+    if unit.as_secs() > 0 {
+        return Duration::from_secs(unit.as_secs() * count);
+    }
+
+    let per_sec = 1_000_000_000 / u64::from(unit.subsec_nanos());
+    Duration::new(
+        count / per_sec,
+        (count % per_sec) as u32 * unit.subsec_nanos(),
+    )
+}
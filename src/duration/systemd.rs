@@ -0,0 +1,330 @@
+//! This module parses and formats time spans the way [`systemd.time(7)`] does,
+//! (e.g. "2h 30min", "1y 2month", "5 usec", "infinity")
+//!
+//! # Example
+//! ```
+//! use humanize_rs::duration::systemd::{format, parse, TimeSpan};
+//! use std::time::Duration;
+//!
+//! let span = parse("2h 30min").unwrap();
+//! assert_eq!(span, TimeSpan::Finite(Duration::from_secs(9000)));
+//! assert_eq!(format(&span), "2h 30min");
+//! assert_eq!(parse("infinity"), Ok(TimeSpan::Infinity));
+//! ```
+//!
+//! [`systemd.time(7)`]: https://www.freedesktop.org/software/systemd/man/systemd.time.html
+
+use std::time::Duration;
+use ParseError;
+
+const USEC_INFINITY: u64 = u64::MAX;
+const USEC_PER_MSEC: u64 = 1_000;
+const USEC_PER_SEC: u64 = 1_000_000;
+const USEC_PER_MINUTE: u64 = 60 * USEC_PER_SEC;
+const USEC_PER_HOUR: u64 = 60 * USEC_PER_MINUTE;
+const USEC_PER_DAY: u64 = 24 * USEC_PER_HOUR;
+const USEC_PER_WEEK: u64 = 7 * USEC_PER_DAY;
+// 30.44 days
+const USEC_PER_MONTH: u64 = 2_629_800 * USEC_PER_SEC;
+// 365.25 days
+const USEC_PER_YEAR: u64 = 31_557_600 * USEC_PER_SEC;
+
+const WHITESPACE: &[u8] = b" \t\n\r";
+
+// suffixes are matched as prefixes in this order, exactly as systemd does
+const PARSE_UNITS: [(&str, u64); 30] = [
+    ("seconds", USEC_PER_SEC),
+    ("second", USEC_PER_SEC),
+    ("sec", USEC_PER_SEC),
+    ("s", USEC_PER_SEC),
+    ("minutes", USEC_PER_MINUTE),
+    ("minute", USEC_PER_MINUTE),
+    ("min", USEC_PER_MINUTE),
+    ("months", USEC_PER_MONTH),
+    ("month", USEC_PER_MONTH),
+    ("M", USEC_PER_MONTH),
+    ("msec", USEC_PER_MSEC),
+    ("ms", USEC_PER_MSEC),
+    ("m", USEC_PER_MINUTE),
+    ("hours", USEC_PER_HOUR),
+    ("hour", USEC_PER_HOUR),
+    ("hr", USEC_PER_HOUR),
+    ("h", USEC_PER_HOUR),
+    ("days", USEC_PER_DAY),
+    ("day", USEC_PER_DAY),
+    ("d", USEC_PER_DAY),
+    ("weeks", USEC_PER_WEEK),
+    ("week", USEC_PER_WEEK),
+    ("w", USEC_PER_WEEK),
+    ("years", USEC_PER_YEAR),
+    ("year", USEC_PER_YEAR),
+    ("y", USEC_PER_YEAR),
+    ("usec", 1),
+    ("us", 1),
+    ("\u{3bc}s", 1), // GREEK SMALL LETTER MU
+    ("\u{b5}s", 1),  // MICRO SIGN
+];
+
+const FORMAT_UNITS: [(&str, u64); 9] = [
+    ("y", USEC_PER_YEAR),
+    ("month", USEC_PER_MONTH),
+    ("w", USEC_PER_WEEK),
+    ("d", USEC_PER_DAY),
+    ("h", USEC_PER_HOUR),
+    ("min", USEC_PER_MINUTE),
+    ("s", USEC_PER_SEC),
+    ("ms", USEC_PER_MSEC),
+    ("us", 1),
+];
+
+/// A systemd time span, which is either finite or "infinity"
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TimeSpan {
+    /// A finite span, with microsecond precision
+    Finite(Duration),
+
+    /// The "infinity" span
+    Infinity,
+}
+
+impl TimeSpan {
+    fn usec(&self) -> u64 {
+        match self {
+            TimeSpan::Finite(d) => {
+                let usec = d
+                    .as_secs()
+                    .checked_mul(USEC_PER_SEC)
+                    .and_then(|res| res.checked_add(u64::from(d.subsec_micros())));
+
+                match usec {
+                    Some(v) => v,
+                    None => USEC_INFINITY,
+                }
+            }
+            TimeSpan::Infinity => USEC_INFINITY,
+        }
+    }
+}
+
+/// Parses a time span using systemd's grammar, values without a unit are seconds
+///
+/// # Example
+/// ```
+/// use humanize_rs::duration::systemd::{parse, TimeSpan};
+/// use std::time::Duration;
+///
+/// assert_eq!(parse("1.5h"), Ok(TimeSpan::Finite(Duration::from_secs(5400))));
+/// assert_eq!(parse("5 usec"), Ok(TimeSpan::Finite(Duration::from_micros(5))));
+/// ```
+pub fn parse(s: &str) -> Result<TimeSpan, ParseError> {
+    let bs = s.as_bytes();
+    let mut read = skip_whitespace(bs, 0);
+
+    if bs[read..].starts_with(b"infinity") {
+        read = skip_whitespace(bs, read + b"infinity".len());
+        if read < bs.len() {
+            return Err(ParseError::Malformed);
+        }
+
+        return Ok(TimeSpan::Infinity);
+    }
+
+    let mut usec: u64 = 0;
+    let mut something = false;
+
+    loop {
+        read = skip_whitespace(bs, read);
+        if read == bs.len() {
+            if !something {
+                return Err(ParseError::EmptyInput);
+            }
+
+            break;
+        }
+
+        if bs[read] == b'-' {
+            return Err(ParseError::InvalidValue);
+        }
+
+        let (v, consumed) = read_int(&bs[read..])?;
+        let dot = read + consumed;
+        let has_fraction = dot < bs.len() && bs[dot] == b'.';
+        if has_fraction {
+            read = skip_digits(bs, dot + 1);
+        } else if consumed == 0 {
+            return Err(ParseError::MissingValue);
+        } else {
+            read = dot;
+        }
+
+        let unit_start = skip_whitespace(bs, read);
+        let (multiplier, unit_end) = match read_unit(&bs[unit_start..]) {
+            Some((m, len)) => (m, unit_start + len),
+            None => (USEC_PER_SEC, unit_start),
+        };
+
+        // "12.34.56" is rejected, while "12.34 .56" and "12.34s.56" are not
+        if unit_end == read && read < bs.len() {
+            return Err(ParseError::InvalidUnit);
+        }
+        read = unit_end;
+
+        if v >= USEC_INFINITY / multiplier {
+            return Err(ParseError::Overflow);
+        }
+        usec = add(usec, v * multiplier)?;
+        something = true;
+
+        if has_fraction {
+            let mut m = multiplier / 10;
+            let mut i = dot + 1;
+            while i < bs.len() && bs[i].is_ascii_digit() {
+                usec = add(usec, u64::from(bs[i] - b'0') * m)?;
+                m /= 10;
+                i += 1;
+            }
+
+            // "3.", "3. 1" and "3.sec" are all invalid
+            if i == dot + 1 {
+                return Err(ParseError::InvalidValue);
+            }
+        }
+    }
+
+    Ok(TimeSpan::Finite(Duration::from_micros(usec)))
+}
+
+/// Formats a time span the way systemd prints it, with microsecond accuracy
+///
+/// # Example
+/// ```
+/// use humanize_rs::duration::systemd::{format, TimeSpan};
+/// use std::time::Duration;
+///
+/// assert_eq!(format(&TimeSpan::Finite(Duration::from_secs(90))), "1min 30s");
+/// assert_eq!(format(&TimeSpan::Infinity), "infinity");
+/// ```
+pub fn format(span: &TimeSpan) -> String {
+    format_with_accuracy(span, Duration::from_micros(1))
+}
+
+/// Formats a time span the way systemd prints it,
+/// parts smaller than `accuracy` are dropped once something has been printed
+pub fn format_with_accuracy(span: &TimeSpan, accuracy: Duration) -> String {
+    let mut t = span.usec();
+    if t == USEC_INFINITY {
+        return "infinity".to_owned();
+    }
+
+    if t == 0 {
+        return "0".to_owned();
+    }
+
+    let accuracy = TimeSpan::Finite(accuracy).usec();
+    let mut out = String::new();
+
+    for &(suffix, unit) in FORMAT_UNITS.iter() {
+        if t == 0 || (t < accuracy && !out.is_empty()) {
+            break;
+        }
+
+        if t < unit {
+            continue;
+        }
+
+        if !out.is_empty() {
+            out.push(' ');
+        }
+
+        let a = t / unit;
+        let mut b = t % unit;
+
+        // spans shorter than one minute are printed in dot notation, like "1.500000s"
+        if t < USEC_PER_MINUTE && b > 0 {
+            let mut j: i32 = 0;
+            let mut cc = unit;
+            while cc > 1 {
+                j += 1;
+                cc /= 10;
+            }
+
+            cc = accuracy;
+            while cc > 1 {
+                b /= 10;
+                j -= 1;
+                cc /= 10;
+            }
+
+            if j > 0 {
+                out.push_str(&format!(
+                    "{}.{:0width$}{}",
+                    a,
+                    b,
+                    suffix,
+                    width = j as usize
+                ));
+                break;
+            }
+        }
+
+        out.push_str(&format!("{}{}", a, suffix));
+        t = b;
+    }
+
+    out
+}
+
+fn add(usec: u64, k: u64) -> Result<u64, ParseError> {
+    if k >= USEC_INFINITY - usec {
+        return Err(ParseError::Overflow);
+    }
+
+    Ok(usec + k)
+}
+
+fn skip_whitespace(bs: &[u8], mut read: usize) -> usize {
+    while read < bs.len() && WHITESPACE.contains(&bs[read]) {
+        read += 1;
+    }
+
+    read
+}
+
+fn skip_digits(bs: &[u8], mut read: usize) -> usize {
+    while read < bs.len() && bs[read].is_ascii_digit() {
+        read += 1;
+    }
+
+    read
+}
+
+// reads a non-negative integer the way strtoll does, an optional '+' is allowed
+fn read_int(bs: &[u8]) -> Result<(u64, usize), ParseError> {
+    let start = if !bs.is_empty() && bs[0] == b'+' {
+        1
+    } else {
+        0
+    };
+    let end = skip_digits(bs, start);
+    if end == start {
+        return Ok((0, 0));
+    }
+
+    let mut v: u64 = 0;
+    for &c in &bs[start..end] {
+        v = v
+            .checked_mul(10)
+            .and_then(|res| res.checked_add(u64::from(c - b'0')))
+            .filter(|res| *res <= i64::MAX as u64)
+            .ok_or(ParseError::Overflow)?;
+    }
+
+    Ok((v, end))
+}
+
+fn read_unit(bs: &[u8]) -> Option<(u64, usize)> {
+    PARSE_UNITS
+        .iter()
+        .find(|&&(suffix, _)| bs.starts_with(suffix.as_bytes()))
+        .map(|&(suffix, usec)| (usec, suffix.len()))
+}
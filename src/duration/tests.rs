@@ -1,4 +1,7 @@
-use super::parse;
+use super::go;
+use super::iso8601;
+use super::systemd::{self, TimeSpan};
+use super::{humanize, parse, round, truncate, Humanizer, Locale, Unit};
 use std::time::Duration;
 use ParseError;
 
@@ -52,11 +55,523 @@ fn test_parse_errors() {
     assert_eq!(parse("1 中文"), Err(ParseError::InvalidUnit));
 
     assert_eq!(parse("100000000000000000000ns"), Err(ParseError::Overflow));
-    assert_eq!(parse("100000000000000000us"), Err(ParseError::Overflow));
-    assert_eq!(parse("100000000000000ms"), Err(ParseError::Overflow));
+    assert_eq!(parse("100000000000000000000us"), Err(ParseError::Overflow));
+    assert_eq!(parse("100000000000000000000ms"), Err(ParseError::Overflow));
 
     assert_eq!(parse("100000000000000000000s"), Err(ParseError::Overflow));
     assert_eq!(parse("10000000000000000000m"), Err(ParseError::Overflow));
     assert_eq!(parse("1000000000000000000h"), Err(ParseError::Overflow));
     assert_eq!(parse("100000000000000000d"), Err(ParseError::Overflow));
+
+    assert_eq!(parse("18446744073709551615s 1s"), Err(ParseError::Overflow));
+    assert_eq!(
+        parse("18446744073709551615s 999999999ns 1ns"),
+        Err(ParseError::Overflow)
+    );
+}
+
+#[test]
+fn test_parse_duration_large_values() {
+    assert_eq!(parse("300000d"), Ok(Duration::from_secs(300000 * 86400)));
+    assert_eq!(
+        parse("18446744073709551615ns"),
+        Ok(Duration::new(18446744073, 709551615))
+    );
+    assert_eq!(
+        parse("100000000000000000us"),
+        Ok(Duration::from_secs(100000000000))
+    );
+    assert_eq!(
+        parse("100000000000000ms"),
+        Ok(Duration::from_secs(100000000000))
+    );
+    assert_eq!(
+        parse("18446744073709551615s"),
+        Ok(Duration::from_secs(18446744073709551615))
+    );
+    assert_eq!(
+        parse("18446744073709551615s 999999999ns"),
+        Ok(Duration::new(18446744073709551615, 999999999))
+    );
+    assert_eq!(parse("1s 999ms 999us 1000ns"), Ok(Duration::from_secs(2)));
+}
+
+#[test]
+fn test_units() {
+    let units = vec![
+        (Unit::Nanosecond, "ns", Duration::from_nanos(1)),
+        (Unit::Microsecond, "us", Duration::from_micros(1)),
+        (Unit::Millisecond, "ms", Duration::from_millis(1)),
+        (Unit::Second, "s", Duration::from_secs(1)),
+        (Unit::Minute, "m", Duration::from_secs(60)),
+        (Unit::Hour, "h", Duration::from_secs(3600)),
+        (Unit::Day, "d", Duration::from_secs(86400)),
+    ];
+
+    for u in units {
+        assert_eq!(u.0.to_string(), u.1);
+        assert_eq!(u.1.parse::<Unit>(), Ok(u.0));
+        assert_eq!(u.0.duration(), u.2);
+    }
+
+    assert_eq!("min".parse::<Unit>(), Err(ParseError::InvalidUnit));
+}
+
+#[test]
+fn test_truncate_and_round() {
+    let d = Duration::new(2 * 86400 + 13 * 3600 + 29 * 60 + 30, 500_500_500);
+    let cases = vec![
+        (Unit::Nanosecond, d, Some(d)),
+        (
+            Unit::Microsecond,
+            Duration::new(d.as_secs(), 500_500_000),
+            Some(Duration::new(d.as_secs(), 500_501_000)),
+        ),
+        (
+            Unit::Millisecond,
+            Duration::new(d.as_secs(), 500_000_000),
+            Some(Duration::new(d.as_secs(), 501_000_000)),
+        ),
+        (
+            Unit::Second,
+            Duration::from_secs(d.as_secs()),
+            Some(Duration::from_secs(d.as_secs() + 1)),
+        ),
+        (
+            Unit::Minute,
+            Duration::from_secs(d.as_secs() - 30),
+            Some(Duration::from_secs(d.as_secs() + 30)),
+        ),
+        (
+            Unit::Hour,
+            Duration::from_secs(2 * 86400 + 13 * 3600),
+            Some(Duration::from_secs(2 * 86400 + 13 * 3600)),
+        ),
+        (
+            Unit::Day,
+            Duration::from_secs(2 * 86400),
+            Some(Duration::from_secs(3 * 86400)),
+        ),
+    ];
+
+    for c in cases {
+        assert_eq!(truncate(d, c.0), c.1, "{}", c.0);
+        assert_eq!(round(d, c.0), c.2, "{}", c.0);
+    }
+
+    assert_eq!(
+        round(Duration::from_millis(1500), Unit::Second),
+        Some(Duration::from_secs(2))
+    );
+    assert_eq!(
+        round(Duration::from_millis(1499), Unit::Second),
+        Some(Duration::from_secs(1))
+    );
+    assert_eq!(
+        round(Duration::new(u64::MAX, 500_000_000), Unit::Second),
+        None
+    );
+    assert_eq!(
+        truncate(Duration::new(u64::MAX, 999_999_999), Unit::Day),
+        Duration::from_secs(u64::MAX - u64::MAX % 86400)
+    );
+}
+
+#[test]
+fn test_humanize() {
+    let cases = vec![
+        (Duration::new(0, 0), "0 seconds"),
+        (Duration::from_millis(300), "less than a second"),
+        (Duration::from_secs(1), "1 second"),
+        (Duration::from_millis(1499), "about 1 second"),
+        (Duration::from_secs(45), "45 seconds"),
+        (Duration::from_secs(60), "1 minute"),
+        (Duration::from_secs(61), "1 minute 1 second"),
+        (Duration::from_millis(119_600), "about 2 minutes"),
+        (Duration::from_secs(3600), "1 hour"),
+        (Duration::from_secs(3629), "about 1 hour"),
+        (Duration::from_secs(3630), "about 1 hour 1 minute"),
+        (Duration::from_secs(7500), "2 hours 5 minutes"),
+        (Duration::from_secs(7510), "about 2 hours 5 minutes"),
+        (Duration::from_secs(86400 - 20), "about 1 day"),
+        (Duration::from_secs(3 * 86400), "3 days"),
+        (Duration::from_secs(3 * 86400 + 7200), "3 days 2 hours"),
+        (Duration::from_secs(400 * 86400 + 1), "about 400 days"),
+    ];
+
+    for c in cases {
+        assert_eq!(humanize(c.0), c.1, "{:?}", c.0);
+    }
+}
+
+#[test]
+fn test_humanizer_options() {
+    let one = Humanizer::new().components(1);
+    assert_eq!(
+        one.format(Duration::from_secs(3 * 86400 - 600)),
+        "about 3 days"
+    );
+    assert_eq!(one.format(Duration::from_secs(90)), "about 2 minutes");
+    assert_eq!(one.format(Duration::from_secs(89)), "about 1 minute");
+
+    let exact = Humanizer::new().approximate(false);
+    assert_eq!(exact.format(Duration::from_secs(7510)), "2 hours 5 minutes");
+
+    let minutes = Humanizer::new().granularity(Unit::Minute);
+    assert_eq!(
+        minutes.format(Duration::from_secs(59)),
+        "less than a minute"
+    );
+    assert_eq!(minutes.format(Duration::from_secs(90)), "about 2 minutes");
+    assert_eq!(
+        Humanizer::new()
+            .granularity(Unit::Hour)
+            .format(Duration::from_secs(60)),
+        "less than an hour"
+    );
+
+    let all = Humanizer::new().components(7).granularity(Unit::Nanosecond);
+    assert_eq!(
+        all.format(Duration::new(86400 + 3600 + 60 + 1, 1_001_001)),
+        "1 day 1 hour 1 minute 1 second 1 millisecond 1 microsecond 1 nanosecond"
+    );
+    assert_eq!(
+        all.format(Duration::new(u64::MAX, 999_999_999)),
+        "213503982334601 days 7 hours 15 seconds 999 milliseconds \
+         999 microseconds 999 nanoseconds"
+    );
+    assert_eq!(
+        Humanizer::new().format(Duration::new(u64::MAX, 999_999_999)),
+        "about 213503982334601 days 7 hours"
+    );
+}
+
+#[test]
+fn test_humanizer_locale() {
+    struct Short;
+
+    impl Locale for Short {
+        fn units(&self, count: u64, unit: Unit) -> String {
+            format!("{}{}", count, unit)
+        }
+
+        fn less_than(&self, unit: Unit) -> String {
+            format!("<1{}", unit)
+        }
+
+        fn about(&self, phrase: &str) -> String {
+            format!("~{}", phrase)
+        }
+
+        fn join(&self, parts: &[String]) -> String {
+            parts.join("")
+        }
+    }
+
+    let h = Humanizer::with_locale(Short);
+    assert_eq!(h.format(Duration::from_secs(7510)), "~2h5m");
+    assert_eq!(h.format(Duration::from_millis(10)), "<1s");
+}
+
+#[test]
+fn test_systemd_parse() {
+    let secs = |s: u64| Ok(TimeSpan::Finite(Duration::from_secs(s)));
+    let micros = |us: u64| Ok(TimeSpan::Finite(Duration::from_micros(us)));
+
+    assert_eq!(systemd::parse("2h 30min"), secs(9000));
+    assert_eq!(systemd::parse("1y 2month"), secs(31557600 + 2 * 2629800));
+    assert_eq!(systemd::parse("5 usec"), micros(5));
+    assert_eq!(systemd::parse("5us"), micros(5));
+    assert_eq!(systemd::parse("5\u{3bc}s"), micros(5));
+    assert_eq!(systemd::parse("5\u{b5}s"), micros(5));
+    assert_eq!(systemd::parse("5ms"), micros(5000));
+    assert_eq!(systemd::parse("5msec"), micros(5000));
+    assert_eq!(systemd::parse("5"), secs(5));
+    assert_eq!(systemd::parse("5s"), secs(5));
+    assert_eq!(systemd::parse("5 sec"), secs(5));
+    assert_eq!(systemd::parse("5 seconds"), secs(5));
+    assert_eq!(systemd::parse("5m"), secs(300));
+    assert_eq!(systemd::parse("5min"), secs(300));
+    assert_eq!(systemd::parse("5 minutes"), secs(300));
+    assert_eq!(systemd::parse("5M"), secs(5 * 2629800));
+    assert_eq!(systemd::parse("5hr"), secs(5 * 3600));
+    assert_eq!(systemd::parse("5 hours"), secs(5 * 3600));
+    assert_eq!(systemd::parse("5d"), secs(5 * 86400));
+    assert_eq!(systemd::parse("5w"), secs(5 * 7 * 86400));
+    assert_eq!(systemd::parse("5 weeks"), secs(5 * 7 * 86400));
+    assert_eq!(systemd::parse("5 years"), secs(5 * 31557600));
+    assert_eq!(systemd::parse(" 1h2min3s "), secs(3723));
+    assert_eq!(systemd::parse("5 6"), secs(11));
+    assert_eq!(systemd::parse("+5s"), secs(5));
+
+    assert_eq!(systemd::parse("1.5h"), secs(5400));
+    assert_eq!(systemd::parse(".5s"), micros(500000));
+    assert_eq!(systemd::parse("0.1234567s"), micros(123456));
+    assert_eq!(systemd::parse("12.34 .56"), micros(12900000));
+    assert_eq!(systemd::parse("12.34s.56"), micros(12900000));
+
+    assert_eq!(systemd::parse("infinity"), Ok(TimeSpan::Infinity));
+    assert_eq!(systemd::parse(" infinity "), Ok(TimeSpan::Infinity));
+}
+
+#[test]
+fn test_systemd_parse_errors() {
+    assert_eq!(systemd::parse(""), Err(ParseError::EmptyInput));
+    assert_eq!(systemd::parse("  "), Err(ParseError::EmptyInput));
+    assert_eq!(systemd::parse("infinity 1s"), Err(ParseError::Malformed));
+    assert_eq!(systemd::parse("-1s"), Err(ParseError::InvalidValue));
+    assert_eq!(systemd::parse("1s -0"), Err(ParseError::InvalidValue));
+    assert_eq!(systemd::parse("s"), Err(ParseError::MissingValue));
+    assert_eq!(systemd::parse("5 foo"), Err(ParseError::MissingValue));
+    assert_eq!(systemd::parse("5foo"), Err(ParseError::InvalidUnit));
+    assert_eq!(systemd::parse("12.34.56"), Err(ParseError::InvalidUnit));
+    assert_eq!(systemd::parse("3."), Err(ParseError::InvalidValue));
+    assert_eq!(systemd::parse("3.sec"), Err(ParseError::InvalidValue));
+    assert_eq!(systemd::parse("3. 1"), Err(ParseError::InvalidValue));
+    assert_eq!(systemd::parse("600000y"), Err(ParseError::Overflow));
+    assert_eq!(
+        systemd::parse("18446744073709551615us"),
+        Err(ParseError::Overflow)
+    );
+    assert_eq!(systemd::parse("500000y 500000y"), Err(ParseError::Overflow));
+}
+
+#[test]
+fn test_systemd_format() {
+    let secs = |s: u64| TimeSpan::Finite(Duration::from_secs(s));
+    let micros = |us: u64| TimeSpan::Finite(Duration::from_micros(us));
+
+    assert_eq!(systemd::format(&secs(9000)), "2h 30min");
+    assert_eq!(systemd::format(&secs(31557600 + 2 * 2629800)), "1y 2month");
+    assert_eq!(systemd::format(&micros(5)), "5us");
+    assert_eq!(systemd::format(&secs(90)), "1min 30s");
+    assert_eq!(systemd::format(&secs(8 * 86400)), "1w 1d");
+    assert_eq!(systemd::format(&micros(86400000001)), "1d 1us");
+    assert_eq!(systemd::format(&micros(1500000)), "1.500000s");
+    assert_eq!(systemd::format(&micros(61500000)), "1min 1.500000s");
+    assert_eq!(systemd::format(&micros(1500)), "1.500ms");
+    assert_eq!(systemd::format(&secs(0)), "0");
+    assert_eq!(systemd::format(&TimeSpan::Infinity), "infinity");
+    assert_eq!(
+        systemd::format(&TimeSpan::Finite(Duration::new(u64::MAX, 0))),
+        "infinity"
+    );
+
+    assert_eq!(
+        systemd::format_with_accuracy(&micros(61500000), Duration::from_secs(1)),
+        "1min 1s"
+    );
+    assert_eq!(
+        systemd::format_with_accuracy(&micros(1500000), Duration::from_millis(1)),
+        "1.500s"
+    );
+}
+
+// cases taken from Go's src/time/time_test.go
+#[test]
+fn test_go_parse() {
+    const NS: i64 = 1;
+    const US: i64 = 1000 * NS;
+    const MS: i64 = 1000 * US;
+    const S: i64 = 1000 * MS;
+    const M: i64 = 60 * S;
+    const H: i64 = 60 * M;
+
+    let cases: Vec<(&str, i64)> = vec![
+        // simple
+        ("0", 0),
+        ("5s", 5 * S),
+        ("30s", 30 * S),
+        ("1478s", 1478 * S),
+        // sign
+        ("-5s", -5 * S),
+        ("+5s", 5 * S),
+        ("-0", 0),
+        ("+0", 0),
+        // decimal
+        ("5.0s", 5 * S),
+        ("5.6s", 5 * S + 600 * MS),
+        ("5.s", 5 * S),
+        (".5s", 500 * MS),
+        ("1.0s", S),
+        ("1.00s", S),
+        ("1.004s", S + 4 * MS),
+        ("1.0040s", S + 4 * MS),
+        ("100.00100s", 100 * S + MS),
+        // different units
+        ("10ns", 10 * NS),
+        ("11us", 11 * US),
+        ("12\u{b5}s", 12 * US),
+        ("12\u{3bc}s", 12 * US),
+        ("13ms", 13 * MS),
+        ("14s", 14 * S),
+        ("15m", 15 * M),
+        ("16h", 16 * H),
+        // composite durations
+        ("3h30m", 3 * H + 30 * M),
+        ("10.5s4m", 4 * M + 10 * S + 500 * MS),
+        ("-2m3.4s", -(2 * M + 3 * S + 400 * MS)),
+        (
+            "1h2m3s4ms5us6ns",
+            H + 2 * M + 3 * S + 4 * MS + 5 * US + 6 * NS,
+        ),
+        ("39h9m14.425s", 39 * H + 9 * M + 14 * S + 425 * MS),
+        // large value
+        ("52763797000ns", 52763797000 * NS),
+        // more than 9 digits after decimal point
+        ("0.3333333333333333333h", 20 * M),
+        // 9007199254740993 = 1<<53+1 cannot be stored precisely in a float64
+        ("9007199254740993ns", ((1 << 53) + 1) * NS),
+        // largest duration that can be represented by int64 in nanoseconds
+        ("9223372036854775807ns", i64::MAX),
+        ("9223372036854775.807us", i64::MAX),
+        ("9223372036s854ms775us807ns", i64::MAX),
+        ("-9223372036854775808ns", i64::MIN),
+        ("-9223372036854775.808us", i64::MIN),
+        ("-9223372036s854ms775us808ns", i64::MIN),
+        // largest negative round trip value
+        ("-2562047h47m16.854775808s", i64::MIN),
+        // huge string
+        ("0.100000000000000000000h", 6 * M),
+        // this value tests the first overflow check in leading_fraction
+        ("0.830103483285477580700h", 49 * M + 48 * S + 372539827 * NS),
+    ];
+
+    for c in cases {
+        assert_eq!(go::parse(c.0), Ok(c.1), "{}", c.0);
+    }
+}
+
+#[test]
+fn test_go_parse_errors() {
+    let cases: Vec<(&str, ParseError)> = vec![
+        ("", ParseError::EmptyInput),
+        ("3", ParseError::MissingUnit),
+        ("-", ParseError::MissingValue),
+        ("s", ParseError::MissingValue),
+        (".", ParseError::MissingValue),
+        ("-.", ParseError::MissingValue),
+        (".s", ParseError::MissingValue),
+        ("+.s", ParseError::MissingValue),
+        ("1d", ParseError::InvalidUnit),
+        ("1 s", ParseError::InvalidUnit),
+        ("\u{fffd}", ParseError::MissingValue),
+        ("\u{fffd} hello \u{fffd} world", ParseError::MissingValue),
+        // overflow
+        ("9223372036854775810ns", ParseError::Overflow),
+        ("9223372036854775808ns", ParseError::Overflow),
+        ("-9223372036854775809ns", ParseError::Overflow),
+        ("9223372036854776us", ParseError::Overflow),
+        ("3000000h", ParseError::Overflow),
+        ("9223372036854775.808us", ParseError::Overflow),
+        ("9223372036854ms775us808ns", ParseError::Overflow),
+    ];
+
+    for c in cases {
+        assert_eq!(go::parse(c.0), Err(c.1), "{}", c.0);
+    }
+}
+
+// cases taken from Go's src/time/time_test.go
+#[test]
+fn test_go_format() {
+    let cases: Vec<(&str, i64)> = vec![
+        ("0s", 0),
+        ("1ns", 1),
+        ("1.1\u{b5}s", 1100),
+        ("2.2ms", 2200 * 1000),
+        ("3.3s", 3300 * 1_000_000),
+        ("4m5s", (4 * 60 + 5) * 1_000_000_000),
+        ("4m5.001s", 4 * 60 * 1_000_000_000 + 5001 * 1_000_000),
+        (
+            "5h6m7.001s",
+            (5 * 3600 + 6 * 60) * 1_000_000_000 + 7001 * 1_000_000,
+        ),
+        ("8m0.000000001s", 8 * 60 * 1_000_000_000 + 1),
+        ("2562047h47m16.854775807s", i64::MAX),
+        ("-2562047h47m16.854775808s", i64::MIN),
+    ];
+
+    for c in cases {
+        assert_eq!(go::format(c.1), c.0, "{}", c.0);
+        assert_eq!(go::parse(c.0), Ok(c.1), "{}", c.0);
+    }
+
+    assert_eq!(go::format(-1100), "-1.1\u{b5}s");
+}
+
+#[test]
+fn test_iso8601_parse() {
+    let cases = vec![
+        ("P1DT2H30M", Duration::from_secs(95400)),
+        ("PT0.5S", Duration::from_millis(500)),
+        ("PT0,5S", Duration::from_millis(500)),
+        ("P2W", Duration::from_secs(14 * 86400)),
+        ("P1W2D", Duration::from_secs(9 * 86400)),
+        ("P1D", Duration::from_secs(86400)),
+        ("PT36H", Duration::from_secs(36 * 3600)),
+        ("PT1M", Duration::from_secs(60)),
+        ("PT0S", Duration::new(0, 0)),
+        ("P0D", Duration::new(0, 0)),
+        ("PT1.5H", Duration::from_secs(5400)),
+        ("P0.5D", Duration::from_secs(43200)),
+        ("PT1H0.25M", Duration::from_secs(3615)),
+        ("PT1.123456789123S", Duration::new(1, 123456789)),
+        (" PT1S ", Duration::from_secs(1)),
+    ];
+
+    for (s, expected) in cases {
+        assert_eq!(iso8601::parse(s), Ok(expected), "{}", s);
+    }
+}
+
+#[test]
+fn test_iso8601_parse_errors() {
+    let cases = vec![
+        ("", ParseError::EmptyInput),
+        ("1D", ParseError::Malformed),
+        ("P", ParseError::MissingValue),
+        ("PT", ParseError::MissingValue),
+        ("P1DT", ParseError::MissingValue),
+        ("PD", ParseError::MissingValue),
+        ("PT.5S", ParseError::MissingValue),
+        ("PT1.S", ParseError::MissingValue),
+        ("P1", ParseError::MissingUnit),
+        ("P1Y", ParseError::InvalidUnit),
+        ("P1M", ParseError::InvalidUnit),
+        ("P1H", ParseError::InvalidUnit),
+        ("PT1D", ParseError::InvalidUnit),
+        ("PT1X", ParseError::InvalidUnit),
+        ("PT1H1H", ParseError::DuplicateUnit),
+        ("PT1S1M", ParseError::Malformed),
+        ("P1DT1HT1M", ParseError::Malformed),
+        ("PT1.5H1M", ParseError::Malformed),
+        ("PT1-S", ParseError::Malformed),
+        ("P99999999999999999999D", ParseError::Overflow),
+        ("P30600000000000W", ParseError::Overflow),
+    ];
+
+    for (s, expected) in cases {
+        assert_eq!(iso8601::parse(s), Err(expected), "{}", s);
+    }
+}
+
+#[test]
+fn test_iso8601_format() {
+    let cases = vec![
+        (Duration::new(0, 0), "PT0S"),
+        (Duration::from_secs(95400), "P1DT2H30M"),
+        (Duration::from_secs(86400), "P1D"),
+        (Duration::from_secs(14 * 86400), "P14D"),
+        (Duration::from_secs(3600), "PT1H"),
+        (Duration::from_secs(61), "PT1M1S"),
+        (Duration::from_millis(1500), "PT1.5S"),
+        (Duration::from_nanos(1), "PT0.000000001S"),
+        (Duration::new(86400, 5), "P1DT0.000000005S"),
+    ];
+
+    for (d, expected) in cases {
+        assert_eq!(iso8601::format(d), expected, "{:?}", d);
+        assert_eq!(iso8601::parse(expected), Ok(d), "{}", expected);
+    }
 }
@@ -2,6 +2,12 @@
 
 //! This lib is used to parse formatted strings to different types
 
+#[cfg(feature = "chrono")]
+extern crate chrono;
+// renamed, as `time` is the module of this crate
+#[cfg(feature = "time")]
+extern crate time as time03;
+
 use std::error::Error;
 use std::fmt;
 
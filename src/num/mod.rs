@@ -3,9 +3,6 @@
 //!
 
 use std::mem::size_of;
-#[cfg(has_i128)]
-use std::{i128, u128};
-use std::{i16, i32, i64, i8, isize, u16, u32, u64, u8, usize};
 
 mod checked;
 pub use self::checked::*;
@@ -1,5 +1,4 @@
 use super::Int;
-use std::i16;
 
 #[test]
 fn test_int_from_u64() {
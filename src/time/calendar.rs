@@ -0,0 +1,115 @@
+use std::str::FromStr;
+use ParseError;
+
+pub(crate) const MONTH_ABBRS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+pub(crate) const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+pub(crate) const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+/// Represents a day of the week
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Weekday {
+    /// Monday
+    Monday,
+
+    /// Tuesday
+    Tuesday,
+
+    /// Wednesday
+    Wednesday,
+
+    /// Thursday
+    Thursday,
+
+    /// Friday
+    Friday,
+
+    /// Saturday
+    Saturday,
+
+    /// Sunday
+    Sunday,
+}
+
+impl Weekday {
+    // returns the day of week of the given days since 0000-01-01, which is a Saturday
+    pub(crate) fn from_days(days: i64) -> Weekday {
+        WEEKDAYS[(days + 5).rem_euclid(7) as usize]
+    }
+
+    /// Returns the English name, (e.g. "Monday")
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Weekday::Monday => "Monday",
+            Weekday::Tuesday => "Tuesday",
+            Weekday::Wednesday => "Wednesday",
+            Weekday::Thursday => "Thursday",
+            Weekday::Friday => "Friday",
+            Weekday::Saturday => "Saturday",
+            Weekday::Sunday => "Sunday",
+        }
+    }
+
+    /// Returns the three-letter English abbreviation, (e.g. "Mon")
+    pub fn abbreviation(&self) -> &'static str {
+        &self.name()[..3]
+    }
+
+    /// Returns the ISO 8601 number of the day, from 1 for Monday to 7 for Sunday
+    pub fn iso_number(&self) -> u32 {
+        *self as u32 + 1
+    }
+
+    /// Returns the day of the ISO 8601 number, from 1 for Monday to 7 for Sunday
+    pub fn from_iso_number(n: u32) -> Option<Weekday> {
+        match n {
+            1..=7 => Some(WEEKDAYS[n as usize - 1]),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Weekday {
+    type Err = ParseError;
+
+    /// Parses the English name or abbreviation, case-insensitive
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WEEKDAYS
+            .iter()
+            .cloned()
+            .find(|w| w.name().eq_ignore_ascii_case(s) || w.abbreviation().eq_ignore_ascii_case(s))
+            .ok_or(ParseError::InvalidValue)
+    }
+}
+
+// returns the month of the three-letter English abbreviation, case-insensitive
+pub(crate) fn month_from_abbr(s: &str) -> Option<u32> {
+    MONTH_ABBRS
+        .iter()
+        .position(|m| m.eq_ignore_ascii_case(s))
+        .map(|i| i as u32 + 1)
+}
@@ -0,0 +1,45 @@
+// conversions from and to the types of chrono 0.4, with the feature "chrono"
+
+use super::{Time, TimeZone, NANOS_PER_SEC};
+use chrono::{self, DateTime, FixedOffset, Utc};
+use std::convert::TryFrom;
+use ParseError;
+
+impl From<TimeZone> for FixedOffset {
+    fn from(tz: TimeZone) -> FixedOffset {
+        // both are offsets of less than a day
+        FixedOffset::east_opt(tz.offset()).expect("offset out of range")
+    }
+}
+
+impl From<FixedOffset> for TimeZone {
+    fn from(offset: FixedOffset) -> TimeZone {
+        TimeZone::from_offset(offset.local_minus_utc()).expect("offset out of range")
+    }
+}
+
+impl From<Time> for DateTime<Utc> {
+    /// Converts the time, which is always in the range of chrono, the years -262143 to 262142
+    fn from(t: Time) -> DateTime<Utc> {
+        DateTime::from_timestamp(t.timestamp(), t.nano).expect("time out of range")
+    }
+}
+
+impl From<Time> for DateTime<FixedOffset> {
+    /// Converts the time at the offset 0, see `DateTime::with_timezone` for other offsets
+    fn from(t: Time) -> DateTime<FixedOffset> {
+        DateTime::<Utc>::from(t).into()
+    }
+}
+
+impl<Tz: chrono::TimeZone> TryFrom<DateTime<Tz>> for Time {
+    type Error = ParseError;
+
+    /// Converts the time, the offset is dropped and a leap second is clamped
+    /// to the end of the previous second, as by `LeapSecond::Clamp`.
+    /// Returns `ParseError::Overflow` if it is out of range.
+    fn try_from(dt: DateTime<Tz>) -> Result<Time, ParseError> {
+        let nano = dt.timestamp_subsec_nanos().min(NANOS_PER_SEC as u32 - 1);
+        Time::from_timestamp(dt.timestamp(), nano).ok_or(ParseError::Overflow)
+    }
+}
@@ -0,0 +1,470 @@
+use super::calendar::Weekday;
+use super::{
+    civil_from_days, days_from_civil, in_range, is_day_validate, is_leap_year, read_expanded_year,
+    read_nano, read_u32, resolve_local, Disambiguation, Time, Zone, NANOS_PER_SEC, SECS_PER_DAY,
+    SECS_PER_HOUR, SECS_PER_MINUTE,
+};
+use std::fmt;
+use std::str::FromStr;
+use ParseError;
+
+// the years of Time::MIN and Time::MAX
+const MIN_YEAR: i32 = -99999;
+const MAX_YEAR: i32 = 99999;
+
+/// Represents a date of the proleptic Gregorian calendar, without time of day and timezone,
+/// the year is from -99999 to 99999 like the dates of `Time`
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{parse_rfc3339, Date, TimeOfDay, TimeZone};
+///
+/// let date = "2018-09-21".parse::<Date>().unwrap();
+/// assert_eq!((date.year(), date.month(), date.day()), (2018, 9, 21));
+/// assert_eq!(date.to_string(), "2018-09-21");
+///
+/// let time = "16:56:44.234".parse::<TimeOfDay>().unwrap();
+/// assert_eq!(
+///     date.at(time, TimeZone::new(8).unwrap()),
+///     parse_rfc3339("2018-09-21T16:56:44.234+08:00").ok()
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Returns the date, or None if the year is out of -99999 to 99999,
+    /// or the month or the day is invalid
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Date> {
+        if !(MIN_YEAR..=MAX_YEAR).contains(&year)
+            || !in_range(month, 1, 12)
+            || day < 1
+            || !is_day_validate(is_leap(year), month, day)
+        {
+            return None;
+        }
+
+        Some(Date { year, month, day })
+    }
+
+    /// Returns the year
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Returns the month, from 1 to 12
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// Returns the day of month, from 1 to 31
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Returns whether the year of the date is a leap year
+    pub fn is_leap_year(&self) -> bool {
+        is_leap(self.year)
+    }
+
+    /// Returns the day of week
+    pub fn weekday(&self) -> Weekday {
+        Weekday::from_days(self.days())
+    }
+
+    /// Returns the time of the date at the time of day in the zone, or None if it is out of range.
+    /// A time of day skipped or repeated in the zone is resolved by `Disambiguation::Compatible`,
+    /// see [`Zone::resolve`].
+    ///
+    /// # Example
+    /// ```
+    /// use humanize_rs::time::{parse_rfc3339, Date, PosixTz, TimeOfDay};
+    ///
+    /// let tz = "EST5EDT,M3.2.0,M11.1.0".parse::<PosixTz>().unwrap();
+    /// let date = Date::new(2018, 3, 11).unwrap();
+    /// // skipped as the clocks are turned forward at 2am
+    /// assert_eq!(
+    ///     date.at(TimeOfDay::new(2, 30, 0, 0).unwrap(), &tz),
+    ///     parse_rfc3339("2018-03-11T03:30:00-04:00").ok()
+    /// );
+    /// ```
+    ///
+    /// [`Zone::resolve`]: ./trait.Zone.html#method.resolve
+    pub fn at<Z: Zone>(&self, time: TimeOfDay, zone: Z) -> Option<Time> {
+        let local = self.days() * SECS_PER_DAY as i64 + time.seconds() as i64;
+        resolve_local(&zone, local, time.nano, None, Disambiguation::Compatible)
+    }
+
+    /// Returns the date `years` later, or earlier if negative,
+    /// February 29 is resolved by `policy` in a common year.
+    /// Returns None if it is rejected or out of range.
+    pub fn checked_add_years(&self, years: i64, policy: DayOverflow) -> Option<Date> {
+        self.checked_add_months(years.checked_mul(12)?, policy)
+    }
+
+    /// Returns the date `months` later, or earlier if negative,
+    /// a day beyond the end of the month is resolved by `policy`.
+    /// Returns None if it is rejected or out of range.
+    ///
+    /// # Example
+    /// ```
+    /// use humanize_rs::time::{Date, DayOverflow};
+    ///
+    /// let date = Date::new(2018, 1, 31).unwrap();
+    /// assert_eq!(
+    ///     date.checked_add_months(1, DayOverflow::Clamp),
+    ///     Date::new(2018, 2, 28)
+    /// );
+    /// assert_eq!(
+    ///     date.checked_add_months(1, DayOverflow::Roll),
+    ///     Date::new(2018, 3, 3)
+    /// );
+    /// assert_eq!(date.checked_add_months(1, DayOverflow::Reject), None);
+    /// assert_eq!(
+    ///     date.checked_add_months(-2, DayOverflow::Clamp),
+    ///     Date::new(2017, 11, 30)
+    /// );
+    /// ```
+    pub fn checked_add_months(&self, months: i64, policy: DayOverflow) -> Option<Date> {
+        let total = (self.year as i64 * 12 + self.month as i64 - 1).checked_add(months)?;
+        let year = total.div_euclid(12);
+        let month = total.rem_euclid(12) as u32 + 1;
+        if year < MIN_YEAR as i64 || year > MAX_YEAR as i64 {
+            return None;
+        }
+
+        let year = year as i32;
+        if let Some(date) = Date::new(year, month, self.day) {
+            return Some(date);
+        }
+
+        match policy {
+            DayOverflow::Clamp => (28..self.day)
+                .rev()
+                .filter_map(|day| Date::new(year, month, day))
+                .next(),
+            DayOverflow::Roll => Date {
+                year,
+                month,
+                day: 1,
+            }
+            .checked_add_days(self.day as i64 - 1),
+            DayOverflow::Reject => None,
+        }
+    }
+
+    /// Returns the date `days` later, or earlier if negative, or None if it is out of range
+    pub fn checked_add_days(&self, days: i64) -> Option<Date> {
+        let (year, month, day) = civil_from_days(self.days().checked_add(days)?);
+        if year < MIN_YEAR as i64 || year > MAX_YEAR as i64 {
+            return None;
+        }
+
+        Some(Date {
+            year: year as i32,
+            month,
+            day,
+        })
+    }
+
+    // returns the days since 0000-01-01
+    pub(crate) fn days(&self) -> i64 {
+        days_from_civil(self.year as i64, self.month, self.day)
+    }
+
+    // returns the date of the days since 0000-01-01
+    pub(crate) fn from_days(days: i64) -> Date {
+        let (year, month, day) = civil_from_days(days);
+        Date {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+}
+
+impl FromStr for Date {
+    type Err = ParseError;
+
+    /// Parses a date in the extended format "YYYY-MM-DD", or the basic format "YYYYMMDD",
+    /// the year may be expanded with a sign and up to 6 digits, (e.g. "-0044-03-15", "+012345-01-01")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bs = s.trim().as_bytes();
+        if let Some((year, n)) = read_expanded_year(bs)? {
+            let bs = &bs[n..];
+            if bs.len() != 6 || bs[3] != b'-' {
+                return Err(ParseError::Malformed);
+            }
+
+            return Date::new(year as i32, read_u32(&bs[1..3])?, read_u32(&bs[4..6])?)
+                .ok_or(ParseError::Overflow);
+        }
+
+        let (y, m, d) = match bs.len() {
+            0 => return Err(ParseError::EmptyInput),
+            10 if bs[4] == b'-' && bs[7] == b'-' => (&bs[0..4], &bs[5..7], &bs[8..10]),
+            8 => (&bs[0..4], &bs[4..6], &bs[6..8]),
+            n if n < 8 => return Err(ParseError::TooShort),
+            n if n > 10 => return Err(ParseError::TooLong),
+            _ => return Err(ParseError::Malformed),
+        };
+
+        Date::new(read_u32(y)? as i32, read_u32(m)?, read_u32(d)?).ok_or(ParseError::Overflow)
+    }
+}
+
+impl fmt::Display for Date {
+    /// Formats the date as "YYYY-MM-DD"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_year(f, self.year)?;
+        write!(f, "-{:02}-{:02}", self.month, self.day)
+    }
+}
+
+/// Policy for a day of month missing after adding months or years to a date,
+/// (e.g. "2018-01-31" plus one month)
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum DayOverflow {
+    /// Clamps the day to the end of the month, (e.g. "2018-02-28")
+    #[default]
+    Clamp,
+
+    /// Rolls the extra days over into the next month, (e.g. "2018-03-03")
+    Roll,
+
+    /// Rejects the result
+    Reject,
+}
+
+/// Represents a time of day, without date and timezone
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::TimeOfDay;
+///
+/// let t = "16:56:44,5".parse::<TimeOfDay>().unwrap();
+/// assert_eq!(t, TimeOfDay::new(16, 56, 44, 500_000_000).unwrap());
+/// assert_eq!(t.to_string(), "16:56:44.5");
+/// assert_eq!("16:56".parse::<TimeOfDay>().unwrap().to_string(), "16:56:00");
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct TimeOfDay {
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nano: u32,
+}
+
+impl TimeOfDay {
+    /// Represents 00:00:00
+    pub const MIDNIGHT: TimeOfDay = TimeOfDay {
+        hour: 0,
+        minute: 0,
+        second: 0,
+        nano: 0,
+    };
+
+    /// Returns the time of day, or None if any component is out of range
+    pub fn new(hour: u32, minute: u32, second: u32, nano: u32) -> Option<TimeOfDay> {
+        if hour > 23 || minute > 59 || second > 59 || nano >= NANOS_PER_SEC as u32 {
+            return None;
+        }
+
+        Some(TimeOfDay {
+            hour,
+            minute,
+            second,
+            nano,
+        })
+    }
+
+    /// Returns the hour, from 0 to 23
+    pub fn hour(&self) -> u32 {
+        self.hour
+    }
+
+    /// Returns the minute, from 0 to 59
+    pub fn minute(&self) -> u32 {
+        self.minute
+    }
+
+    /// Returns the second, from 0 to 59
+    pub fn second(&self) -> u32 {
+        self.second
+    }
+
+    /// Returns the nanosecond, from 0 to 999,999,999
+    pub fn nano(&self) -> u32 {
+        self.nano
+    }
+
+    // returns the seconds since midnight
+    fn seconds(&self) -> u64 {
+        self.hour as u64 * SECS_PER_HOUR + self.minute as u64 * SECS_PER_MINUTE + self.second as u64
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = ParseError;
+
+    /// Parses a time of day "hh:mm[:ss[.fraction]]", a comma may be the decimal sign,
+    /// digits of the fraction beyond nanoseconds are truncated
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bs = s.trim().as_bytes();
+        if bs.is_empty() {
+            return Err(ParseError::EmptyInput);
+        }
+
+        if bs.len() < 5 {
+            return Err(ParseError::TooShort);
+        }
+
+        if bs[2] != b':' || (bs.len() > 5 && (bs.len() < 8 || bs[5] != b':')) {
+            return Err(ParseError::Malformed);
+        }
+
+        let hour = read_u32(&bs[0..2])?;
+        let minute = read_u32(&bs[3..5])?;
+        let second = match bs.len() {
+            5 => 0,
+            _ => read_u32(&bs[6..8])?,
+        };
+
+        let nano = match bs.get(8) {
+            None => 0,
+            Some(b'.') | Some(b',') => {
+                let (nano, read) = read_nano(&bs[9..]);
+                if read == 0 {
+                    return Err(ParseError::MissingValue);
+                }
+                if 9 + read != bs.len() {
+                    return Err(ParseError::InvalidValue);
+                }
+                nano
+            }
+            Some(_) => return Err(ParseError::Malformed),
+        };
+
+        TimeOfDay::new(hour, minute, second, nano).ok_or(ParseError::Overflow)
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    /// Formats the time of day as "hh:mm:ss", with the fraction of second if it is not zero
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if self.nano == 0 {
+            return Ok(());
+        }
+
+        let fraction = format!("{:09}", self.nano);
+        write!(f, ".{}", fraction.trim_end_matches('0'))
+    }
+}
+
+impl Time {
+    /// Returns the date of the time in the zone
+    pub fn to_date<Z: Zone>(&self, zone: Z) -> Date {
+        Date::from_days(self.local_days(zone.offset_at(self)).0)
+    }
+
+    /// Returns the time `years` later, or earlier if negative, at the same local time of day
+    /// in the zone, see [`Date::checked_add_years`] and [`Time::checked_add_days`].
+    ///
+    /// [`Date::checked_add_years`]: ./struct.Date.html#method.checked_add_years
+    /// [`Time::checked_add_days`]: #method.checked_add_days
+    pub fn checked_add_years<Z: Zone>(
+        &self,
+        years: i64,
+        policy: DayOverflow,
+        zone: Z,
+    ) -> Option<Time> {
+        self.shift_date(zone, |date| date.checked_add_years(years, policy))
+    }
+
+    /// Returns the time `months` later, or earlier if negative, at the same local time of day
+    /// in the zone, see [`Date::checked_add_months`] and [`Time::checked_add_days`].
+    ///
+    /// # Example
+    /// ```
+    /// use humanize_rs::time::{parse_rfc3339, DayOverflow, TimeZone};
+    ///
+    /// let t = parse_rfc3339("2018-01-31T16:00:00Z").unwrap();
+    /// let tz = TimeZone::new(8).unwrap();
+    /// assert_eq!(
+    ///     t.checked_add_months(1, DayOverflow::Clamp, TimeZone::utc()),
+    ///     parse_rfc3339("2018-02-28T16:00:00Z").ok()
+    /// );
+    /// // 2018-02-01T00:00:00 in the timezone
+    /// assert_eq!(
+    ///     t.checked_add_months(1, DayOverflow::Clamp, tz),
+    ///     parse_rfc3339("2018-03-01T00:00:00+08:00").ok()
+    /// );
+    /// ```
+    ///
+    /// [`Date::checked_add_months`]: ./struct.Date.html#method.checked_add_months
+    /// [`Time::checked_add_days`]: #method.checked_add_days
+    pub fn checked_add_months<Z: Zone>(
+        &self,
+        months: i64,
+        policy: DayOverflow,
+        zone: Z,
+    ) -> Option<Time> {
+        self.shift_date(zone, |date| date.checked_add_months(months, policy))
+    }
+
+    /// Returns the time `days` later, or earlier if negative, at the same local time of day
+    /// in the zone, or None if it is out of range.
+    /// The offset of the time is kept if the zone shows it at the result,
+    /// otherwise the local result is resolved by `Disambiguation::Compatible`.
+    pub fn checked_add_days<Z: Zone>(&self, days: i64, zone: Z) -> Option<Time> {
+        self.shift_date(zone, |date| date.checked_add_days(days))
+    }
+
+    // moves the local date of the time, keeping the local time of day
+    fn shift_date<Z, F>(&self, zone: Z, f: F) -> Option<Time>
+    where
+        Z: Zone,
+        F: FnOnce(Date) -> Option<Date>,
+    {
+        let timezone = zone.offset_at(self);
+        let (days, secs) = self.local_days(timezone);
+        let local = f(Date::from_days(days))?.days() * SECS_PER_DAY as i64 + secs as i64;
+        resolve_local(
+            &zone,
+            local,
+            self.nano,
+            Some(timezone),
+            Disambiguation::Compatible,
+        )
+    }
+
+    /// Returns the time of day of the time in the zone
+    pub fn time_of_day<Z: Zone>(&self, zone: Z) -> TimeOfDay {
+        let secs = self.local_days(zone.offset_at(self)).1;
+        TimeOfDay {
+            hour: secs / 3600,
+            minute: secs / 60 % 60,
+            second: secs % 60,
+            nano: self.nano,
+        }
+    }
+}
+
+// returns whether the year is a leap year, which repeats every 400 years
+fn is_leap(year: i32) -> bool {
+    is_leap_year(year.rem_euclid(400) as u32)
+}
+
+// writes a year of 4 digits, with a sign if it is out of [0, 9999]
+pub(crate) fn write_year<W: fmt::Write>(w: &mut W, year: i32) -> fmt::Result {
+    match year {
+        0..=9999 => write!(w, "{:04}", year),
+        y if y < 0 => write!(w, "-{:04}", -(y as i64)),
+        y => write!(w, "+{}", y),
+    }
+}
@@ -0,0 +1,461 @@
+use super::calendar::{Weekday, MONTH_ABBRS, MONTH_NAMES, WEEKDAYS};
+use super::date::write_year;
+use super::{
+    civil_from_days, days_from_civil, parse_offset, Date, Disambiguation, LeapSecond, LocalResult,
+    Time, TimeOfDay, TimeZone, Zone,
+};
+use std::fmt::Write;
+use std::str::FromStr;
+use ParseError;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Item {
+    Literal(String),
+    Year,
+    ShortYear,
+    Month,
+    MonthAbbr,
+    MonthName,
+    Day,
+    PaddedDay,
+    Ordinal,
+    WeekdayAbbr,
+    WeekdayName,
+    Hour,
+    Hour12,
+    AmPm,
+    Minute,
+    Second,
+    // the number of digits, or None for 1 to 9 digits
+    Fraction(Option<usize>),
+    Offset,
+    ColonOffset,
+}
+
+/// A strftime-like format description, for both parsing and formatting times
+///
+/// | Specifier | Meaning | Example |
+/// |-----------|---------|---------|
+/// | `%Y` | year, 4 digits, or a sign and 4 to 6 digits if it is out of 0000 to 9999 | 2018 |
+/// | `%y` | year without century, 69-99 are 1969-1999 and 00-68 are 2000-2068 | 18 |
+/// | `%m` | month, 2 digits | 09 |
+/// | `%b` | month abbreviation | Sep |
+/// | `%B` | month name | September |
+/// | `%d` | day of month, 2 digits | 01 |
+/// | `%e` | day of month, padded with a space | " 1" |
+/// | `%j` | day of year, 3 digits | 264 |
+/// | `%a` | weekday abbreviation | Fri |
+/// | `%A` | weekday name | Friday |
+/// | `%H` | hour, 2 digits | 16 |
+/// | `%I` | hour of the 12-hour clock, 2 digits | 04 |
+/// | `%p` | "AM" or "PM" | PM |
+/// | `%M` | minute, 2 digits | 56 |
+/// | `%S` | second, 2 digits | 44 |
+/// | `%f` | nanoseconds, 9 digits | 234867232 |
+/// | `%3f`, `%6f`, `%9f` | milliseconds, microseconds or nanoseconds, exactly 3, 6 or 9 digits | 234 |
+/// | `%z` | offset, "+hhmm" | +0800 |
+/// | `%:z` | offset, "+hh:mm" | +08:00 |
+/// | `%F` | `%Y-%m-%d` | 2018-09-21 |
+/// | `%T` | `%H:%M:%S` | 16:56:44 |
+/// | `%%` | a literal "%" | % |
+///
+/// When parsing, numbers may have fewer digits than their width,
+/// `%f` reads 1 to 9 digits while `%3f`, `%6f` and `%9f` read exactly their digits,
+/// `%z` also accepts "+hh:mm", "+hh" and "Z", and names are case-insensitive. Weekdays are read but not checked against the date.
+/// Fields missing from the description default to 1970-01-01T00:00:00,
+/// in the timezone of the format, which is UTC by default.
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{parse_rfc3339, Format, TimeZone};
+///
+/// let f = Format::new("%d/%b/%Y:%H:%M:%S %z").unwrap();
+/// let t = f.parse("21/Sep/2018:16:56:44 +0800").unwrap();
+/// assert_eq!(Ok(&t), parse_rfc3339("2018-09-21T16:56:44+08:00").as_ref());
+/// assert_eq!(
+///     f.format(&t, TimeZone::utc()),
+///     "21/Sep/2018:08:56:44 +0000"
+/// );
+///
+/// let f = Format::new("%Y.%m.%d %Hh%M").unwrap();
+/// assert_eq!(f.parse("2018.09.21 16h56"), parse_rfc3339("2018-09-21T16:56:00Z"));
+///
+/// assert!(Format::new("%Y-%Q").is_err());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Format {
+    items: Vec<Item>,
+    timezone: TimeZone,
+}
+
+// the fields read by a format
+#[derive(Debug, Default)]
+pub(crate) struct Fields {
+    pub(crate) year: Option<i32>,
+    month: Option<u32>,
+    day: Option<u32>,
+    ordinal: Option<u32>,
+    hour: u32,
+    hour12: Option<u32>,
+    pm: Option<bool>,
+    minute: u32,
+    second: u32,
+    nano: u32,
+    offset: Option<TimeZone>,
+}
+
+impl Format {
+    /// Returns the format of the description, or an error if it has unknown specifiers
+    pub fn new(description: &str) -> Result<Format, ParseError> {
+        let mut items = Vec::new();
+        let mut literal = String::new();
+        let mut chars = description.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                literal.push(c);
+                continue;
+            }
+
+            let spec = chars.next().ok_or(ParseError::Malformed)?;
+            if spec == '%' {
+                literal.push('%');
+                continue;
+            }
+
+            if !literal.is_empty() {
+                items.push(Item::Literal(literal.split_off(0)));
+            }
+
+            match spec {
+                'Y' => items.push(Item::Year),
+                'y' => items.push(Item::ShortYear),
+                'm' => items.push(Item::Month),
+                'b' => items.push(Item::MonthAbbr),
+                'B' => items.push(Item::MonthName),
+                'd' => items.push(Item::Day),
+                'e' => items.push(Item::PaddedDay),
+                'j' => items.push(Item::Ordinal),
+                'a' => items.push(Item::WeekdayAbbr),
+                'A' => items.push(Item::WeekdayName),
+                'H' => items.push(Item::Hour),
+                'I' => items.push(Item::Hour12),
+                'p' => items.push(Item::AmPm),
+                'M' => items.push(Item::Minute),
+                'S' => items.push(Item::Second),
+                'f' => items.push(Item::Fraction(None)),
+                'z' => items.push(Item::Offset),
+                '3' | '6' | '9' => {
+                    if chars.next() != Some('f') {
+                        return Err(ParseError::Malformed);
+                    }
+                    items.push(Item::Fraction(Some(spec as usize - '0' as usize)));
+                }
+                ':' => {
+                    if chars.next() != Some('z') {
+                        return Err(ParseError::Malformed);
+                    }
+                    items.push(Item::ColonOffset);
+                }
+                'F' => {
+                    items.push(Item::Year);
+                    items.push(Item::Literal("-".to_owned()));
+                    items.push(Item::Month);
+                    items.push(Item::Literal("-".to_owned()));
+                    items.push(Item::Day);
+                }
+                'T' => {
+                    items.push(Item::Hour);
+                    items.push(Item::Literal(":".to_owned()));
+                    items.push(Item::Minute);
+                    items.push(Item::Literal(":".to_owned()));
+                    items.push(Item::Second);
+                }
+                _ => return Err(ParseError::Malformed),
+            }
+        }
+
+        if !literal.is_empty() {
+            items.push(Item::Literal(literal));
+        }
+
+        Ok(Format {
+            items,
+            timezone: TimeZone::utc(),
+        })
+    }
+
+    /// Sets the timezone of parsed times without an offset, UTC by default
+    pub fn timezone(mut self, timezone: TimeZone) -> Format {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Parses a time in the format
+    pub fn parse(&self, s: &str) -> Result<Time, ParseError> {
+        self.parse_fields(s)?
+            .to_time(&self.timezone, Disambiguation::Compatible)
+    }
+
+    /// Parses a time in the format, reading one without offset as the wall clock time in `zone`,
+    /// which is resolved by `disambiguation` if it is skipped or repeated there,
+    /// see [`Rfc3339Parser::parse_in`].
+    ///
+    /// # Example
+    /// ```
+    /// use humanize_rs::time::{parse_rfc3339, Disambiguation, Format, PosixTz};
+    ///
+    /// let zone = "CST6CDT,M3.2.0,M11.1.0".parse::<PosixTz>().unwrap();
+    /// let f = Format::new("%d/%m/%Y %H:%M").unwrap();
+    /// assert_eq!(
+    ///     f.parse_in("21/09/2018 16:56", &zone, Disambiguation::Compatible),
+    ///     parse_rfc3339("2018-09-21T16:56:00-05:00")
+    /// );
+    /// ```
+    ///
+    /// [`Rfc3339Parser::parse_in`]: ./struct.Rfc3339Parser.html#method.parse_in
+    pub fn parse_in<Z: Zone + ?Sized>(
+        &self,
+        s: &str,
+        zone: &Z,
+        disambiguation: Disambiguation,
+    ) -> Result<Time, ParseError> {
+        self.parse_fields(s)?.to_time(zone, disambiguation)
+    }
+
+    /// Formats the time in the given zone, the seconds of the offset are dropped
+    /// as in the `Display` of `TimeZone`, and the local time is in the offset of whole minutes
+    pub fn format<Z: Zone>(&self, t: &Time, zone: Z) -> String {
+        let timezone = zone.offset_at(t).whole_minutes();
+        let (days, secs) = t.local_days(timezone);
+        let (year, month, day) = civil_from_days(days);
+        let hour = secs / 3600;
+
+        let mut out = String::new();
+        for item in &self.items {
+            // writing to a String never fails
+            let _ = match *item {
+                Item::Literal(ref s) => write!(out, "{}", s),
+                Item::Year => write_year(&mut out, year as i32),
+                Item::ShortYear => write!(out, "{:02}", year.rem_euclid(100)),
+                Item::Month => write!(out, "{:02}", month),
+                Item::MonthAbbr => write!(out, "{}", MONTH_ABBRS[month as usize - 1]),
+                Item::MonthName => write!(out, "{}", MONTH_NAMES[month as usize - 1]),
+                Item::Day => write!(out, "{:02}", day),
+                Item::PaddedDay => write!(out, "{:2}", day),
+                Item::Ordinal => write!(out, "{:03}", days - days_from_civil(year, 1, 1) + 1),
+                Item::WeekdayAbbr => write!(out, "{}", Weekday::from_days(days).abbreviation()),
+                Item::WeekdayName => write!(out, "{}", Weekday::from_days(days).name()),
+                Item::Hour => write!(out, "{:02}", hour),
+                Item::Hour12 => write!(out, "{:02}", (hour + 11) % 12 + 1),
+                Item::AmPm => write!(out, "{}", if hour < 12 { "AM" } else { "PM" }),
+                Item::Minute => write!(out, "{:02}", secs / 60 % 60),
+                Item::Second => write!(out, "{:02}", secs % 60),
+                Item::Fraction(n) => {
+                    let n = n.unwrap_or(9);
+                    write!(out, "{:01$}", t.nano / 10_u32.pow(9 - n as u32), n)
+                }
+                Item::Offset => write!(out, "{:#}", timezone),
+                Item::ColonOffset => write!(out, "{:+}", timezone),
+            };
+        }
+
+        out
+    }
+
+    // reads the fields of the input
+    pub(crate) fn parse_fields(&self, s: &str) -> Result<Fields, ParseError> {
+        if s.is_empty() {
+            return Err(ParseError::EmptyInput);
+        }
+
+        let mut fields = Fields::default();
+        let mut bs = s.as_bytes();
+
+        for item in &self.items {
+            if bs.is_empty() {
+                return Err(ParseError::TooShort);
+            }
+
+            match *item {
+                Item::Literal(ref lit) => {
+                    if !bs.starts_with(lit.as_bytes()) {
+                        return Err(ParseError::Malformed);
+                    }
+                    bs = &bs[lit.len()..];
+                }
+                Item::Year => fields.year = Some(read_year(&mut bs)?),
+                Item::ShortYear => {
+                    let y = read_number(&mut bs, 2)? as i32;
+                    fields.year = Some(if y < 69 { 2000 + y } else { 1900 + y });
+                }
+                Item::Month => fields.month = Some(read_number(&mut bs, 2)?),
+                Item::MonthAbbr => fields.month = Some(read_name(&mut bs, &MONTH_ABBRS)? + 1),
+                Item::MonthName => fields.month = Some(read_name(&mut bs, &MONTH_NAMES)? + 1),
+                Item::Day => fields.day = Some(read_number(&mut bs, 2)?),
+                Item::PaddedDay => {
+                    if bs[0] == b' ' {
+                        bs = &bs[1..];
+                    }
+                    fields.day = Some(read_number(&mut bs, 2)?);
+                }
+                Item::Ordinal => fields.ordinal = Some(read_number(&mut bs, 3)?),
+                Item::WeekdayAbbr => {
+                    let names: Vec<&str> = WEEKDAYS.iter().map(|w| w.abbreviation()).collect();
+                    read_name(&mut bs, &names)?;
+                }
+                Item::WeekdayName => {
+                    let names: Vec<&str> = WEEKDAYS.iter().map(|w| w.name()).collect();
+                    read_name(&mut bs, &names)?;
+                }
+                Item::Hour => fields.hour = read_number(&mut bs, 2)?,
+                Item::Hour12 => fields.hour12 = Some(read_number(&mut bs, 2)?),
+                Item::AmPm => fields.pm = Some(read_name(&mut bs, &["AM", "PM"])? == 1),
+                Item::Minute => fields.minute = read_number(&mut bs, 2)?,
+                Item::Second => fields.second = read_number(&mut bs, 2)?,
+                Item::Fraction(n) => {
+                    let digits = count_digits(bs, n.unwrap_or(9));
+                    if digits == 0 || n.is_some_and(|n| digits != n) {
+                        return Err(ParseError::InvalidValue);
+                    }
+                    fields.nano = read_number(&mut bs, digits)? * 10_u32.pow(9 - digits as u32);
+                }
+                Item::Offset | Item::ColonOffset => {
+                    let i = match bs[0] {
+                        b'Z' | b'z' => 1,
+                        _ => 1 + count_offset(&bs[1..]),
+                    };
+                    fields.offset = Some(parse_offset(&bs[..i], true)?);
+                    bs = &bs[i..];
+                }
+            }
+        }
+
+        if !bs.is_empty() {
+            return Err(ParseError::TooLong);
+        }
+
+        Ok(fields)
+    }
+}
+
+impl FromStr for Format {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Format::new(s)
+    }
+}
+
+impl Fields {
+    // returns the time of the fields, a missing offset is resolved in the given zone
+    pub(crate) fn to_time<Z: Zone + ?Sized>(
+        &self,
+        zone: &Z,
+        disambiguation: Disambiguation,
+    ) -> Result<Time, ParseError> {
+        let year = self.year.unwrap_or(1970);
+        let date = match (self.ordinal, self.month, self.day) {
+            (Some(ordinal), None, None) => Date::from_ordinal(year, ordinal),
+            _ => Date::new(year, self.month.unwrap_or(1), self.day.unwrap_or(1)),
+        };
+        let date = date.ok_or(ParseError::Overflow)?;
+
+        let mut hour = self.hour;
+        if let Some(h) = self.hour12 {
+            if h == 0 || h > 12 {
+                return Err(ParseError::Overflow);
+            }
+            hour = h % 12;
+        }
+        if self.pm == Some(true) {
+            hour += 12;
+        }
+
+        // a leap second is clamped to the last nanosecond of the minute before
+        let leap = self.second == 60;
+        let second = if leap { 59 } else { self.second };
+        let time =
+            TimeOfDay::new(hour, self.minute, second, self.nano).ok_or(ParseError::Overflow)?;
+
+        let t = match self.offset {
+            Some(offset) => date.at(time, offset).ok_or(ParseError::Overflow)?,
+            None => {
+                let wall = date.at(time, TimeZone::utc()).ok_or(ParseError::Overflow)?;
+                match zone.local(&wall) {
+                    LocalResult::None => return Err(ParseError::Overflow),
+                    _ => zone
+                        .resolve(&wall, disambiguation)
+                        .ok_or(ParseError::InvalidValue)?,
+                }
+            }
+        };
+        if leap {
+            return LeapSecond::Clamp.apply(t);
+        }
+
+        Ok(t)
+    }
+}
+
+fn count_digits(bs: &[u8], max: usize) -> usize {
+    bs.iter()
+        .take(max)
+        .take_while(|c| c.is_ascii_digit())
+        .count()
+}
+
+// counts the bytes of "hh", "hhmm" or "hh:mm"
+fn count_offset(bs: &[u8]) -> usize {
+    let hh = count_digits(bs, 2);
+    if hh < 2 || bs.len() == 2 {
+        return hh;
+    }
+
+    match bs[2] {
+        b':' => 3 + count_digits(&bs[3..], 2),
+        _ => 2 + count_digits(&bs[2..], 2),
+    }
+}
+
+// reads 1 to `width` digits
+fn read_number(bs: &mut &[u8], width: usize) -> Result<u32, ParseError> {
+    let n = count_digits(bs, width);
+    if n == 0 {
+        return Err(ParseError::InvalidValue);
+    }
+
+    let v = bs[..n].iter().fold(0, |v, c| v * 10 + u32::from(c - b'0'));
+    *bs = &bs[n..];
+    Ok(v)
+}
+
+// reads a year of 1 to 4 digits, or a sign and 4 to 6 digits as written for other years
+fn read_year(bs: &mut &[u8]) -> Result<i32, ParseError> {
+    let neg = match bs[0] {
+        b'+' => false,
+        b'-' => true,
+        _ => return Ok(read_number(bs, 4)? as i32),
+    };
+
+    *bs = &bs[1..];
+    if count_digits(bs, 6) < 4 {
+        return Err(ParseError::InvalidValue);
+    }
+
+    let year = read_number(bs, 6)? as i32;
+    Ok(if neg { -year } else { year })
+}
+
+// reads one of the names, case-insensitive, returns its index
+fn read_name(bs: &mut &[u8], names: &[&str]) -> Result<u32, ParseError> {
+    for (i, name) in names.iter().enumerate() {
+        let n = name.len();
+        if bs.len() >= n && bs[..n].eq_ignore_ascii_case(name.as_bytes()) {
+            *bs = &bs[n..];
+            return Ok(i as u32);
+        }
+    }
+
+    Err(ParseError::InvalidValue)
+}
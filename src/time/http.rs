@@ -0,0 +1,202 @@
+use super::calendar::{month_from_abbr, Weekday, MONTH_ABBRS};
+use super::{civil_from_days, read_u32, LeapSecond, Time, TimeZone};
+use std::time::Duration;
+use ParseError;
+
+/// Parses a [`HTTP-date`], in any of the three accepted formats:
+/// - IMF-fixdate, (e.g. "Sun, 06 Nov 1994 08:49:37 GMT")
+/// - obsolete RFC 850 format, (e.g. "Sunday, 06-Nov-94 08:49:37 GMT")
+/// - ANSI C's asctime() format, (e.g. "Sun Nov  6 08:49:37 1994")
+///
+/// The day of week is not checked, 2-digit years are 1950 to 2049,
+/// and a leap second is clamped to the end of the previous second.
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{parse_http_date, parse_rfc3339};
+///
+/// let t = parse_rfc3339("1994-11-06T08:49:37Z");
+/// assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"), t);
+/// assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), t);
+/// assert_eq!(parse_http_date("Sun Nov  6 08:49:37 1994"), t);
+/// ```
+///
+/// [`HTTP-date`]: https://tools.ietf.org/html/rfc7231#section-7.1.1.1
+pub fn parse_http_date(s: &str) -> Result<Time, ParseError> {
+    let tokens: Vec<&str> = s.split_whitespace().collect();
+    if tokens.is_empty() {
+        return Err(ParseError::EmptyInput);
+    }
+
+    let (day, month, year, clock) = match tokens.len() {
+        // IMF-fixdate
+        6 => {
+            read_weekday(tokens[0], true)?;
+            read_zone(tokens[5])?;
+            (
+                read_number(tokens[1], 2)?,
+                tokens[2],
+                read_number(tokens[3], 4)?,
+                tokens[4],
+            )
+        }
+        // rfc850-date
+        4 => {
+            read_weekday(tokens[0], true)?;
+            read_zone(tokens[3])?;
+            let date: Vec<&str> = tokens[1].split('-').collect();
+            if date.len() != 3 {
+                return Err(ParseError::Malformed);
+            }
+
+            let year = match read_number(date[2], 2)? {
+                y if y < 50 => y + 2000,
+                y => y + 1900,
+            };
+            (read_number(date[0], 2)?, date[1], year, tokens[2])
+        }
+        // asctime-date
+        5 => {
+            read_weekday(tokens[0], false)?;
+            // the day is padded with a space, (e.g. "Nov  6")
+            let day = read_number(tokens[2], tokens[2].len().min(2))?;
+            (day, tokens[1], read_number(tokens[4], 4)?, tokens[3])
+        }
+        _ => return Err(ParseError::Malformed),
+    };
+
+    let month = month_from_abbr(month).ok_or(ParseError::InvalidValue)?;
+
+    let clock = clock.as_bytes();
+    if clock.len() != 8 || clock[2] != b':' || clock[5] != b':' {
+        return Err(ParseError::Malformed);
+    }
+
+    let hour = read_u32(&clock[0..2])?;
+    let minute = read_u32(&clock[3..5])?;
+    let second = read_u32(&clock[6..8])?;
+
+    let utc = TimeZone::utc();
+    if second == 60 {
+        let t = Time::from_timetuple(year, month, day, hour, minute, 59, 0, utc)
+            .ok_or(ParseError::Overflow)?;
+        return LeapSecond::Clamp.apply(t);
+    }
+
+    Time::from_timetuple(year, month, day, hour, minute, second, 0, utc).ok_or(ParseError::Overflow)
+}
+
+/// Formats the time as an IMF-fixdate [`HTTP-date`], (e.g. "Sun, 06 Nov 1994 08:49:37 GMT"),
+/// the fraction of second is dropped.
+///
+/// Returns None if the year is out of 0000 to 9999, which has no 4-digit form.
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{format_http_date, Time};
+///
+/// let t = "1994-11-06T16:49:37.5+08:00".parse::<Time>().unwrap();
+/// assert_eq!(
+///     format_http_date(&t).unwrap(),
+///     "Sun, 06 Nov 1994 08:49:37 GMT"
+/// );
+/// assert_eq!(format_http_date(&Time::MAX), None);
+/// ```
+///
+/// [`HTTP-date`]: https://tools.ietf.org/html/rfc7231#section-7.1.1.1
+pub fn format_http_date(t: &Time) -> Option<String> {
+    let (days, secs) = t.local_days(TimeZone::utc());
+    let (year, month, day) = civil_from_days(days);
+    if !(0..=9999).contains(&year) {
+        return None;
+    }
+
+    Some(format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+        Weekday::from_days(days).abbreviation(),
+        day,
+        MONTH_ABBRS[month as usize - 1],
+        year,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    ))
+}
+
+/// Represents the value of a [`Retry-After`] header
+///
+/// [`Retry-After`]: https://tools.ietf.org/html/rfc7231#section-7.1.3
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RetryAfter {
+    /// An HTTP-date after which to retry
+    Date(Time),
+
+    /// A delay after receiving the response
+    Delay(Duration),
+}
+
+impl RetryAfter {
+    /// Returns the time to retry, the delay is counted from `received`,
+    /// returns None if it is out of range
+    pub fn time(&self, received: &Time) -> Option<Time> {
+        match *self {
+            RetryAfter::Date(t) => Some(t),
+            RetryAfter::Delay(d) => received.checked_add(d),
+        }
+    }
+}
+
+/// Parses the value of a [`Retry-After`] header, either an HTTP-date or delay-seconds
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{parse_retry_after, parse_rfc3339, RetryAfter};
+/// use std::time::Duration;
+///
+/// assert_eq!(
+///     parse_retry_after("120"),
+///     Ok(RetryAfter::Delay(Duration::from_secs(120)))
+/// );
+/// assert_eq!(
+///     parse_retry_after("Fri, 31 Dec 1999 23:59:59 GMT"),
+///     parse_rfc3339("1999-12-31T23:59:59Z").map(RetryAfter::Date)
+/// );
+/// ```
+///
+/// [`Retry-After`]: https://tools.ietf.org/html/rfc7231#section-7.1.3
+pub fn parse_retry_after(s: &str) -> Result<RetryAfter, ParseError> {
+    let s = s.trim();
+    if !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit()) {
+        let secs = s.parse::<u64>().or(Err(ParseError::Overflow))?;
+        return Ok(RetryAfter::Delay(Duration::from_secs(secs)));
+    }
+
+    parse_http_date(s).map(RetryAfter::Date)
+}
+
+// reads a day name, which is followed by a comma in IMF-fixdate and rfc850-date
+fn read_weekday(s: &str, comma: bool) -> Result<Weekday, ParseError> {
+    let s = match (comma, s.strip_suffix(',')) {
+        (true, Some(name)) => name,
+        (false, None) => s,
+        _ => return Err(ParseError::Malformed),
+    };
+
+    s.parse::<Weekday>()
+}
+
+fn read_zone(s: &str) -> Result<(), ParseError> {
+    if s != "GMT" {
+        return Err(ParseError::InvalidTimezone);
+    }
+
+    Ok(())
+}
+
+fn read_number(s: &str, digits: usize) -> Result<u32, ParseError> {
+    if s.len() != digits {
+        return Err(ParseError::InvalidValue);
+    }
+
+    read_u32(s.as_bytes())
+}
@@ -0,0 +1,260 @@
+use super::{format_rfc3339, parse_rfc3339, DayOverflow, Time, TimeZone};
+use duration::iso8601;
+use std::cmp;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use ParseError;
+
+/// A half-open time interval [start, end), the end is not contained
+///
+/// It is parsed from the [`ISO 8601`] forms "start/end", "start/duration" and "duration/end",
+/// where the times are RFC3339 datetime strings, see [`parse_rfc3339`],
+/// and the duration is an ISO 8601 duration, see [`iso8601::parse`].
+/// The years and months of the duration, (e.g. "P1Y2M10D"), are calendar years and months in UTC,
+/// they are added before the rest, with the day of month clamped, see [`Time::checked_add_months`].
+/// The range "start..end" is accepted as well.
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{parse_rfc3339, Interval};
+/// use std::time::Duration;
+///
+/// let day = "2018-09-21T00:00Z/P1D".parse::<Interval>().unwrap();
+/// assert_eq!(day, "2018-09-21T00:00Z..2018-09-22T00:00Z".parse().unwrap());
+/// assert_eq!(day, "PT24H/2018-09-22T00:00Z".parse().unwrap());
+/// assert_eq!(day.to_string(), "2018-09-21T00:00:00Z/2018-09-22T00:00:00Z");
+///
+/// assert!(day.contains(&parse_rfc3339("2018-09-21T16:56:44+08:00").unwrap()));
+/// assert!(!day.contains(day.end()));
+///
+/// let hours: Vec<_> = day.steps(Duration::from_secs(3600)).collect();
+/// assert_eq!(hours.len(), 24);
+/// assert_eq!(hours[23], parse_rfc3339("2018-09-21T23:00:00Z").unwrap());
+///
+/// let month = "2018-01-31T00:00Z/P1M".parse::<Interval>().unwrap();
+/// assert_eq!(month.end(), &parse_rfc3339("2018-02-28T00:00Z").unwrap());
+/// ```
+///
+/// [`ISO 8601`]: https://en.wikipedia.org/wiki/ISO_8601#Time_intervals
+/// [`parse_rfc3339`]: ./fn.parse_rfc3339.html
+/// [`iso8601::parse`]: ../duration/iso8601/fn.parse.html
+/// [`Time::checked_add_months`]: ./struct.Time.html#method.checked_add_months
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Interval {
+    start: Time,
+    end: Time,
+}
+
+impl Interval {
+    /// Returns the interval from start to end, or None if end is before start
+    pub fn new(start: Time, end: Time) -> Option<Interval> {
+        if end < start {
+            return None;
+        }
+
+        Some(Interval { start, end })
+    }
+
+    /// Returns the interval of the duration from start, or None if the end is out of range
+    pub fn from_start(start: Time, d: Duration) -> Option<Interval> {
+        let end = start.checked_add(d)?;
+        Some(Interval { start, end })
+    }
+
+    /// Returns the interval of the duration until end, or None if the start is out of range
+    pub fn until_end(d: Duration, end: Time) -> Option<Interval> {
+        let start = end.checked_sub(d)?;
+        Some(Interval { start, end })
+    }
+
+    /// Returns the start of the interval, which is contained
+    pub fn start(&self) -> &Time {
+        &self.start
+    }
+
+    /// Returns the end of the interval, which is not contained
+    pub fn end(&self) -> &Time {
+        &self.end
+    }
+
+    /// Returns the length of the interval
+    pub fn duration(&self) -> Duration {
+        super::sub(&self.end, &self.start)
+    }
+
+    /// Returns whether the interval is empty, i.e. its start is its end
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns whether the time is in the interval
+    pub fn contains(&self, t: &Time) -> bool {
+        self.start <= *t && *t < self.end
+    }
+
+    /// Returns whether the intervals share any time
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        // an empty interval shares no time even if it is inside the other
+        !self.is_empty() && !other.is_empty() && self.start < other.end && other.start < self.end
+    }
+
+    /// Returns the times in both intervals, or None if they do not overlap
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        if !self.overlaps(other) {
+            return None;
+        }
+
+        Some(Interval {
+            start: cmp::max(self.start, other.start),
+            end: cmp::min(self.end, other.end),
+        })
+    }
+
+    /// Returns an iterator over the times from the start, `step` apart, before the end
+    ///
+    /// # Panics
+    /// Panics if the step is zero.
+    pub fn steps(&self, step: Duration) -> Steps {
+        assert!(step != Duration::new(0, 0), "step must not be zero");
+
+        Steps {
+            next: Some(self.start),
+            end: self.end,
+            step,
+        }
+    }
+}
+
+impl FromStr for Interval {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseError::EmptyInput);
+        }
+
+        if let Some(i) = s.find("..") {
+            let start = parse_part(&s[..i], parse_rfc3339)?;
+            let end = parse_part(&s[i + 2..], parse_rfc3339)?;
+            return Interval::new(start, end).ok_or(ParseError::InvalidValue);
+        }
+
+        let i = s.find('/').ok_or(ParseError::Malformed)?;
+        let (first, second) = (&s[..i], &s[i + 1..]);
+        if first.starts_with('P') {
+            let (months, d) = parse_part(first, parse_duration)?;
+            let end = parse_part(second, parse_rfc3339)?;
+            // the duration is subtracted in the reverse order
+            let start = end
+                .checked_sub(d)
+                .and_then(|t| t.checked_add_months(-months, DayOverflow::Clamp, TimeZone::utc()))
+                .ok_or(ParseError::Overflow)?;
+            return Interval::new(start, end).ok_or(ParseError::Overflow);
+        }
+
+        let start = parse_part(first, parse_rfc3339)?;
+        if second.starts_with('P') {
+            let (months, d) = parse_part(second, parse_duration)?;
+            let end = start
+                .checked_add_months(months, DayOverflow::Clamp, TimeZone::utc())
+                .and_then(|t| t.checked_add(d))
+                .ok_or(ParseError::Overflow)?;
+            return Interval::new(start, end).ok_or(ParseError::Overflow);
+        }
+
+        let end = parse_part(second, parse_rfc3339)?;
+        Interval::new(start, end).ok_or(ParseError::InvalidValue)
+    }
+}
+
+impl fmt::Display for Interval {
+    /// Formats the interval as "start/end" in UTC, (e.g. "2018-09-21T00:00:00Z/2018-09-22T00:00:00Z")
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}",
+            format_rfc3339(&self.start, TimeZone::utc()),
+            format_rfc3339(&self.end, TimeZone::utc())
+        )
+    }
+}
+
+/// An iterator over the times of an interval, see [`Interval::steps`]
+///
+/// [`Interval::steps`]: ./struct.Interval.html#method.steps
+#[derive(Debug, Clone)]
+pub struct Steps {
+    next: Option<Time>,
+    end: Time,
+    step: Duration,
+}
+
+impl Iterator for Steps {
+    type Item = Time;
+
+    fn next(&mut self) -> Option<Time> {
+        let t = self.next.take().filter(|t| *t < self.end)?;
+        self.next = t.checked_add(self.step);
+        Some(t)
+    }
+}
+
+// parses a side of an interval, which must not be empty
+fn parse_part<T, F>(s: &str, parse: F) -> Result<T, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    if s.trim().is_empty() {
+        return Err(ParseError::MissingValue);
+    }
+
+    parse(s)
+}
+
+// parses an ISO 8601 duration into its calendar months, of the leading years and months,
+// and the exact duration of the rest, (e.g. "P1Y2M10D" to 14 months and 10 days)
+fn parse_duration(s: &str) -> Result<(i64, Duration), ParseError> {
+    let s = s.trim();
+    let mut rest = s.get(1..).unwrap_or("");
+    let mut months: i64 = 0;
+    // index of the next allowed designator of "YM"
+    let mut next = 0;
+
+    loop {
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let i = match rest.as_bytes().get(digits) {
+            Some(&c) if digits > 0 => match b"YM".iter().position(|&d| d == c) {
+                Some(i) => i,
+                None => break,
+            },
+            _ => break,
+        };
+
+        if i + 1 == next {
+            return Err(ParseError::DuplicateUnit);
+        }
+        if i < next {
+            return Err(ParseError::Malformed);
+        }
+        next = i + 1;
+
+        let n: i64 = rest[..digits].parse().map_err(|_| ParseError::Overflow)?;
+        let n = if i == 0 { n.checked_mul(12) } else { Some(n) };
+        months = n
+            .and_then(|n| months.checked_add(n))
+            .ok_or(ParseError::Overflow)?;
+        rest = &rest[digits + 1..];
+    }
+
+    if next == 0 {
+        return iso8601::parse(s).map(|d| (0, d));
+    }
+    if rest.is_empty() {
+        return Ok((months, Duration::new(0, 0)));
+    }
+
+    let d = iso8601::parse(&format!("P{}", rest))?;
+    Ok((months, d))
+}
@@ -0,0 +1,136 @@
+#[cfg(feature = "tzdb")]
+use super::Tz;
+use super::{PosixTz, Time, TimeZone, Zone};
+use std::env;
+#[cfg(feature = "tzdb")]
+use std::fs;
+use std::time::SystemTime;
+
+#[cfg(feature = "tzdb")]
+const LOCALTIME_PATH: &str = "/etc/localtime";
+
+/// The time zone of the machine, see [`LocalZone::detect`]
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{Disambiguation, LocalZone, Rfc3339Parser, Time, Zone};
+///
+/// let zone = LocalZone::detect();
+/// let t = Rfc3339Parser::new()
+///     .parse_in("2018-09-21 16:56:44", &zone, Disambiguation::Compatible)
+///     .unwrap();
+/// assert_eq!(zone.components(&t).hour, 16);
+///
+/// let now = zone.components(&Time::now());
+/// assert!(now.year >= 2018);
+/// ```
+///
+/// [`LocalZone::detect`]: ./enum.LocalZone.html#method.detect
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LocalZone {
+    /// A fixed offset, which is UTC if no zone is detected
+    Fixed(TimeZone),
+
+    /// A POSIX TZ string in the environment variable `TZ`
+    Posix(PosixTz),
+
+    /// A zone of the IANA time zone database
+    #[cfg(feature = "tzdb")]
+    Tz(Tz),
+}
+
+impl LocalZone {
+    /// Detects the time zone of the machine, like the C library does:
+    /// - the value of the environment variable `TZ` if it is set, see [`LocalZone::from_tz_var`]
+    /// - otherwise the TZif file "/etc/localtime"
+    ///
+    /// Returns UTC if neither gives a zone. Files are only read with the feature `tzdb`.
+    ///
+    /// [`LocalZone::from_tz_var`]: ./enum.LocalZone.html#method.from_tz_var
+    pub fn detect() -> LocalZone {
+        let zone = match env::var("TZ") {
+            Ok(value) => LocalZone::from_tz_var(&value),
+            Err(_) => LocalZone::from_localtime(),
+        };
+
+        zone.unwrap_or(LocalZone::Fixed(TimeZone::utc()))
+    }
+
+    /// Returns the zone of a value of the environment variable `TZ`:
+    /// - an empty value is UTC
+    /// - ":name" is the named zone of the tz database, or the TZif file at an absolute path
+    /// - otherwise a POSIX TZ string, (e.g. "CST6CDT,M3.2.0,M11.1.0"),
+    ///   or else a named zone, (e.g. "Asia/Shanghai")
+    ///
+    /// Returns None if the value gives no zone.
+    pub fn from_tz_var(value: &str) -> Option<LocalZone> {
+        if value.is_empty() {
+            return Some(LocalZone::Fixed(TimeZone::utc()));
+        }
+
+        if let Some(name) = value.strip_prefix(':') {
+            return LocalZone::from_name(name);
+        }
+
+        match PosixTz::new(value) {
+            Ok(tz) => Some(LocalZone::Posix(tz)),
+            Err(_) => LocalZone::from_name(value),
+        }
+    }
+
+    #[cfg(feature = "tzdb")]
+    fn from_name(name: &str) -> Option<LocalZone> {
+        let tz = if name.starts_with('/') {
+            Tz::load_file(name, name)
+        } else {
+            Tz::load(name)
+        };
+
+        tz.ok().map(LocalZone::Tz)
+    }
+
+    #[cfg(not(feature = "tzdb"))]
+    fn from_name(_: &str) -> Option<LocalZone> {
+        None
+    }
+
+    // reads "/etc/localtime", which is usually a link into the zoneinfo directory
+    // naming the zone, (e.g. "/usr/share/zoneinfo/Asia/Shanghai")
+    #[cfg(feature = "tzdb")]
+    fn from_localtime() -> Option<LocalZone> {
+        let name = fs::read_link(LOCALTIME_PATH)
+            .ok()
+            .and_then(|target| {
+                let target = target.to_str()?;
+                let i = target.find("zoneinfo/")?;
+                Some(target[i + "zoneinfo/".len()..].to_owned())
+            })
+            .unwrap_or_else(|| "localtime".to_owned());
+
+        Tz::load_file(&name, LOCALTIME_PATH).ok().map(LocalZone::Tz)
+    }
+
+    #[cfg(not(feature = "tzdb"))]
+    fn from_localtime() -> Option<LocalZone> {
+        None
+    }
+}
+
+impl Zone for LocalZone {
+    fn offset_at(&self, t: &Time) -> TimeZone {
+        match *self {
+            LocalZone::Fixed(tz) => tz,
+            LocalZone::Posix(ref tz) => tz.offset_at(t),
+            #[cfg(feature = "tzdb")]
+            LocalZone::Tz(ref tz) => tz.offset_at(t),
+        }
+    }
+}
+
+impl Time {
+    /// Returns the current time of the system clock,
+    /// a clock out of range saturates to `Time::MIN` or `Time::MAX`
+    pub fn now() -> Time {
+        Time::saturating_from_system_time(SystemTime::now())
+    }
+}
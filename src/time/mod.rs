@@ -21,51 +21,86 @@
 //!
 //! [`RFC3339`]: https://tools.ietf.org/html/rfc3339
 
+mod calendar;
+#[cfg(feature = "chrono")]
+mod chrono_compat;
+mod date;
+mod format;
+mod http;
+mod interval;
+mod local;
+mod natural;
+mod posix;
+mod presets;
+mod relative;
+mod rfc2822;
+#[cfg(feature = "time")]
+mod time_compat;
 mod timezone;
-
+#[cfg(feature = "tzdb")]
+mod tzif;
+mod unix;
+mod week;
+mod zone;
+
+pub use self::calendar::*;
+pub use self::date::*;
+pub use self::format::*;
+pub use self::http::*;
+pub use self::interval::*;
+pub use self::local::*;
+pub use self::natural::*;
+pub use self::posix::*;
+pub use self::presets::*;
+pub use self::relative::*;
+pub use self::rfc2822::*;
 pub use self::timezone::*;
-
+#[cfg(feature = "tzdb")]
+pub use self::tzif::*;
+pub use self::unix::*;
+pub use self::week::*;
+pub use self::zone::*;
+
+use self::timezone::parse_offset;
+use self::zone::resolve_local;
+use duration::Unit;
 use std::cmp::Ordering;
-use std::str::{from_utf8, FromStr};
+use std::convert::TryFrom;
+use std::str::FromStr;
 use std::time::{Duration, SystemTime};
 use ParseError;
 
-const MAX_SECONDS: u64 = 315569433600;
+// the seconds since 0000-01-01T00:00:00Z of -99999-01-01T00:00:00Z and +100000-01-01T00:00:00Z,
+// the range keeps the microseconds since 1970-01-01 in i64
+const MIN_SECONDS: i64 = -3155663577600;
+const MAX_SECONDS: i64 = 3155695200000;
 const UNIX_EPOCH: Time = Time {
-    sec: 62167132800,
+    sec: 62167219200,
     nano: 0,
 };
 
+const NANOS_PER_SEC: i64 = 1_000_000_000;
 const SECS_PER_MINUTE: u64 = 60;
 const SECS_PER_HOUR: u64 = 60 * SECS_PER_MINUTE;
 const SECS_PER_DAY: u64 = 24 * SECS_PER_HOUR;
 const DAYS_PER_400_YEARS: u32 = 365 * 400 + 97;
-const DAYS_PER_100_YEARS: u32 = 365 * 100 + 24;
-const DAYS_PER_4_YEARS: u32 = 365 * 4 + 1;
-const DAYS_BEFORE: [u32; 13] = [
-    0,
-    31,
-    31 + 28,
-    31 + 28 + 31,
-    31 + 28 + 31 + 30,
-    31 + 28 + 31 + 30 + 31,
-    31 + 28 + 31 + 30 + 31 + 30,
-    31 + 28 + 31 + 30 + 31 + 30 + 31,
-    31 + 28 + 31 + 30 + 31 + 30 + 31 + 31,
-    31 + 28 + 31 + 30 + 31 + 30 + 31 + 31 + 30,
-    31 + 28 + 31 + 30 + 31 + 30 + 31 + 31 + 30 + 31,
-    31 + 28 + 31 + 30 + 31 + 30 + 31 + 31 + 30 + 31 + 30,
-    31 + 28 + 31 + 30 + 31 + 30 + 31 + 31 + 30 + 31 + 30 + 31,
-];
 
 const DATE_TIME_FORMAT_MIN_LENGTH: usize = 10; // "2006-01-02"
 const DATE_TIME_FORMAT_WITH_TIME: usize = 19; // "2006-01-02T15:04:05"
 const DATE_TIME_FORMAT_MAX_LENGTH: usize = 35; // "2006-01-02T15:04:05.999999999Z07:00"
 
-/// Represents a time in range [0000-01-01T00:00:00Z, 10000-01-01T00:00:00Z)
-#[derive(Debug, Eq, PartialEq)]
+/// Represents a time in range [-99999-01-01T00:00:00Z, +100000-01-01T00:00:00Z)
+/// of the proleptic Gregorian calendar, the year before 1 is 0 as in ISO 8601
+///
+/// With the features "chrono" and "time", it converts from and to `chrono::DateTime`
+/// and `time::OffsetDateTime` of time 0.3 by `From` and `TryFrom`,
+/// and [`TimeZone`] from and to their offsets.
+///
+/// [`TimeZone`]: ./struct.TimeZone.html
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Time {
-    sec: u64,
+    // seconds since 0000-01-01T00:00:00Z, negative before it
+    sec: i64,
     nano: u32,
 }
 
@@ -73,7 +108,25 @@ impl Time {
     /// Represents `1970-01-01 00:00:00Z`
     pub const UNIX_EPOCH: Time = UNIX_EPOCH;
 
-    /// Returns a Time with the given time tuple
+    /// The earliest time, `-99999-01-01T00:00:00Z`
+    pub const MIN: Time = Time {
+        sec: MIN_SECONDS,
+        nano: 0,
+    };
+
+    /// The latest time, `+99999-12-31T23:59:59.999999999Z`
+    pub const MAX: Time = Time {
+        sec: MAX_SECONDS - 1,
+        nano: NANOS_PER_SEC as u32 - 1,
+    };
+
+    /// Returns a Time with the given time tuple, or None if it is invalid or out of range
+    ///
+    /// The year is unsigned, so it is from 0 to 99999, times of negative years are given
+    /// by [`Date::at`], as `Date::new` takes a signed year.
+    ///
+    /// [`Date::at`]: ./struct.Date.html#method.at
+    #[allow(clippy::too_many_arguments)]
     pub fn from_timetuple(
         year: u32,
         month: u32,
@@ -84,8 +137,7 @@ impl Time {
         nano: u32,
         timezone: TimeZone,
     ) -> Option<Time> {
-        if !in_range(year, 0, 10000)
-            || !in_range(month, 1, 12)
+        if !in_range(month, 1, 12)
             || !in_range(day, 1, 31)
             || !in_range(hour, 0, 23)
             || !in_range(minute, 0, 59)
@@ -95,99 +147,282 @@ impl Time {
             return None;
         }
 
-        let is_leap = is_leap_year(year);
+        let is_leap = is_leap_year(year % 400);
 
         if !is_day_validate(is_leap, month, day) {
             return None;
         }
 
-        let mut d: u32 = 0;
-
-        let mut y = year;
+        let d = days_from_civil(year as i64, month, day);
+        let local = d * SECS_PER_DAY as i64
+            + (hour as u64 * SECS_PER_HOUR + minute as u64 * SECS_PER_MINUTE + second as u64)
+                as i64;
 
-        let mut n: u32 = y / 400;
-        y -= 400 * n;
-        d += DAYS_PER_400_YEARS * n;
+        Time::from_local(local, nano, timezone)
+    }
 
-        n = y / 100;
-        y -= n * 100;
-        d += DAYS_PER_100_YEARS * n;
+    /// Convert the time to SystemTime, returns None if the time is before unix epoch
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        if let Some(d) = self.since(&UNIX_EPOCH) {
+            return Some(SystemTime::UNIX_EPOCH + d);
+        }
 
-        n = y / 4;
-        y -= n * 4;
-        d += DAYS_PER_4_YEARS * n;
+        None
+    }
 
-        n = y;
-        d += 365 * n;
+    /// Returns the time of the given SystemTime, or None if it is out of range
+    pub fn from_system_time(t: SystemTime) -> Option<Time> {
+        match t.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(d) => UNIX_EPOCH.checked_add(d),
+            Err(e) => UNIX_EPOCH.checked_sub(e.duration()),
+        }
+    }
 
-        d += DAYS_BEFORE[(month - 1) as usize];
-        // already calculated in DAYS_PER_XX_YEARS
-        if year > 0 && is_leap && month <= 2 {
-            d -= 1;
+    /// Returns the time of the given SystemTime, clamped to [`Time::MIN`] and [`Time::MAX`]
+    ///
+    /// [`Time::MIN`]: #associatedconstant.MIN
+    /// [`Time::MAX`]: #associatedconstant.MAX
+    pub fn saturating_from_system_time(t: SystemTime) -> Time {
+        match t.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(d) => UNIX_EPOCH.saturating_add(d),
+            Err(e) => UNIX_EPOCH.saturating_sub(e.duration()),
         }
+    }
 
-        d += day - 1;
+    /// Returns the duration since an earlier time, and None if earlier is not before self.
+    pub fn since(&self, earlier: &Time) -> Option<Duration> {
+        self.duration_since(earlier).ok()
+    }
 
-        let mut sec: u64 = d as u64 * SECS_PER_DAY
-            + hour as u64 * SECS_PER_HOUR
-            + minute as u64 * SECS_PER_MINUTE
-            + second as u64;
+    /// Returns the duration since `other` if it is not after self,
+    /// otherwise returns the duration until `other` as the error.
+    ///
+    /// # Example
+    /// ```
+    /// use humanize_rs::time::Time;
+    /// use std::time::Duration;
+    ///
+    /// let t1 = "2018-09-21T16:56:44Z".parse::<Time>().unwrap();
+    /// let t2 = "2018-09-21T16:57:44Z".parse::<Time>().unwrap();
+    /// assert_eq!(t2.duration_since(&t1), Ok(Duration::from_secs(60)));
+    /// assert_eq!(t1.duration_since(&t2), Err(Duration::from_secs(60)));
+    /// ```
+    pub fn duration_since(&self, other: &Time) -> Result<Duration, Duration> {
+        if self < other {
+            return Err(sub(other, self));
+        }
 
-        let offset = timezone.offset();
-        if offset >= 0 {
-            let minus = offset as u64;
-            if minus > sec {
-                return None;
-            }
+        Ok(sub(self, other))
+    }
 
-            sec -= minus;
-        } else {
-            sec += (-offset) as u64;
+    /// Returns the duration since an earlier time, or zero if earlier is after self
+    pub fn saturating_duration_since(&self, earlier: &Time) -> Duration {
+        self.since(earlier).unwrap_or_default()
+    }
+
+    /// Returns the time after the given duration, or None if it is out of range
+    pub fn checked_add(&self, d: Duration) -> Option<Time> {
+        let mut sec = self.sec.checked_add(i64::try_from(d.as_secs()).ok()?)?;
+        let mut nano = self.nano + d.subsec_nanos();
+        if nano >= NANOS_PER_SEC as u32 {
+            sec = sec.checked_add(1)?;
+            nano -= NANOS_PER_SEC as u32;
         }
 
         if sec >= MAX_SECONDS {
             return None;
         }
 
-        Some(Time {
-            sec: sec,
-            nano: nano,
-        })
+        Some(Time { sec, nano })
     }
 
-    /// Convert the time to SystemTime, returns None if the time is before unix epoch
-    pub fn to_system_time(&self) -> Option<SystemTime> {
-        if let Some(d) = self.since(&UNIX_EPOCH) {
-            return Some(SystemTime::UNIX_EPOCH + d);
+    /// Returns the time before the given duration, or None if it is out of range
+    pub fn checked_sub(&self, d: Duration) -> Option<Time> {
+        let mut sec = self.sec.checked_sub(i64::try_from(d.as_secs()).ok()?)?;
+        let mut nano = self.nano;
+        if nano < d.subsec_nanos() {
+            sec = sec.checked_sub(1)?;
+            nano += NANOS_PER_SEC as u32;
         }
+        nano -= d.subsec_nanos();
 
-        None
+        if sec < MIN_SECONDS {
+            return None;
+        }
+
+        Some(Time { sec, nano })
     }
 
-    /// Returns the duration since an earlier time, and None if earlier is not before self.
-    pub fn since(&self, earlier: &Time) -> Option<Duration> {
-        if self < earlier {
-            return None;
+    /// Returns the time after the given duration, or [`Time::MAX`] if it is out of range
+    ///
+    /// # Example
+    /// ```
+    /// use humanize_rs::time::{parse_rfc3339, Time};
+    /// use std::time::Duration;
+    ///
+    /// let t = parse_rfc3339("2018-09-21T16:56:44Z").unwrap();
+    /// assert_eq!(
+    ///     t.saturating_add(Duration::from_secs(60)),
+    ///     parse_rfc3339("2018-09-21T16:57:44Z").unwrap()
+    /// );
+    /// assert_eq!(t.saturating_add(Duration::from_secs(u64::MAX)), Time::MAX);
+    /// assert_eq!(t.saturating_sub(Duration::from_secs(u64::MAX)), Time::MIN);
+    /// ```
+    ///
+    /// [`Time::MAX`]: #associatedconstant.MAX
+    pub fn saturating_add(&self, d: Duration) -> Time {
+        self.checked_add(d).unwrap_or(Time::MAX)
+    }
+
+    /// Returns the time before the given duration, or [`Time::MIN`] if it is out of range
+    ///
+    /// [`Time::MIN`]: #associatedconstant.MIN
+    pub fn saturating_sub(&self, d: Duration) -> Time {
+        self.checked_sub(d).unwrap_or(Time::MIN)
+    }
+
+    /// Returns the result of rounding the time down to a multiple of `unit`,
+    /// the multiples are aligned to midnight in the given zone.
+    /// The offset of the time is kept if the zone shows it at the result,
+    /// otherwise the local result is resolved by `Disambiguation::Compatible`.
+    /// Returns None if the result is out of range.
+    ///
+    /// # Example
+    /// ```
+    /// use humanize_rs::duration::Unit;
+    /// use humanize_rs::time::{PosixTz, Time, TimeZone};
+    ///
+    /// let t = "2018-09-21T16:56:44+08:00".parse::<Time>().unwrap();
+    /// assert_eq!(
+    ///     t.truncate(Unit::Day, TimeZone::new(8).unwrap()),
+    ///     "2018-09-21T00:00:00+08:00".parse::<Time>().ok()
+    /// );
+    ///
+    /// // the clocks are turned back at 2am
+    /// let tz = "EST5EDT,M3.2.0,M11.1.0".parse::<PosixTz>().unwrap();
+    /// let t = "2018-11-04T12:00:00-05:00".parse::<Time>().unwrap();
+    /// assert_eq!(
+    ///     t.truncate(Unit::Day, &tz),
+    ///     "2018-11-04T00:00:00-04:00".parse::<Time>().ok()
+    /// );
+    /// ```
+    pub fn truncate<Z: Zone>(&self, unit: Unit, zone: Z) -> Option<Time> {
+        let timezone = zone.offset_at(self);
+        let local = self.sec + timezone.offset() as i64;
+        let (sec, nano) = truncate_local(local, self.nano, unit.duration());
+
+        resolve_local(&zone, sec, nano, Some(timezone), Disambiguation::Compatible)
+    }
+
+    /// Returns the result of rounding the time to the nearest multiple of `unit`,
+    /// the multiples are aligned to midnight in the given zone, and halfway values are rounded up.
+    /// The result is resolved in the zone as by [`Time::truncate`].
+    /// Returns None if the result is out of range.
+    ///
+    /// [`Time::truncate`]: #method.truncate
+    pub fn round<Z: Zone>(&self, unit: Unit, zone: Z) -> Option<Time> {
+        let timezone = zone.offset_at(self);
+        let unit = unit.duration();
+        let local = self.sec + timezone.offset() as i64;
+        let (mut sec, mut nano) = truncate_local(local, self.nano, unit);
+
+        // both remainder and unit are less than 1 day, which fits in i64 nanoseconds
+        let rem = (local - sec) * NANOS_PER_SEC + self.nano as i64 - nano as i64;
+        let unit_nanos = unit.as_secs() as i64 * NANOS_PER_SEC + unit.subsec_nanos() as i64;
+        if rem * 2 >= unit_nanos {
+            sec += unit.as_secs() as i64;
+            nano += unit.subsec_nanos();
+            if nano >= NANOS_PER_SEC as u32 {
+                sec += 1;
+                nano -= NANOS_PER_SEC as u32;
+            }
         }
 
-        let mut sec = self.sec - earlier.sec;
-        let mut nano = self.nano;
-        if nano < earlier.nano {
-            sec -= 1;
-            nano += 1_000_000_000;
+        resolve_local(&zone, sec, nano, Some(timezone), Disambiguation::Compatible)
+    }
+
+    // returns (year, month, day) of the time in the timezone
+    fn date(&self, timezone: TimeZone) -> (i64, u32, u32) {
+        civil_from_days(self.local_days(timezone).0)
+    }
+
+    // returns (days since 0000-01-01, seconds of the day) of the time in the timezone
+    fn local_days(&self, timezone: TimeZone) -> (i64, u32) {
+        let local = self.sec + timezone.offset() as i64;
+        let days = local.div_euclid(SECS_PER_DAY as i64);
+        (days, (local - days * SECS_PER_DAY as i64) as u32)
+    }
+
+    // returns the time of the given seconds in the timezone
+    fn from_local(local: i64, nano: u32, timezone: TimeZone) -> Option<Time> {
+        let sec = local - timezone.offset() as i64;
+        if !(MIN_SECONDS..MAX_SECONDS).contains(&sec) {
+            return None;
         }
-        nano -= earlier.nano;
 
-        Some(Duration::new(sec, nano))
+        Some(Time { sec, nano })
+    }
+}
+
+// returns the duration from earlier to later, earlier must not be after later
+fn sub(later: &Time, earlier: &Time) -> Duration {
+    let mut sec = (later.sec - earlier.sec) as u64;
+    let mut nano = later.nano;
+    if nano < earlier.nano {
+        sec -= 1;
+        nano += NANOS_PER_SEC as u32;
     }
+    nano -= earlier.nano;
+
+    Duration::new(sec, nano)
+}
+
+// returns the days since 0000-01-01 of the date
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    // shift the start of the year to March, so that leap days are at the end of a year
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * DAYS_PER_400_YEARS as i64 + doe + 60
+}
+
+// returns (year, month, day) of the days since 0000-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // shift the start of the year to March, so that leap days are at the end of a year
+    let z = days - 60;
+    let era = z.div_euclid(DAYS_PER_400_YEARS as i64);
+    let doe = z.rem_euclid(DAYS_PER_400_YEARS as i64);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+// rounds the local time down to a multiple of unit
+fn truncate_local(local: i64, nano: u32, unit: Duration) -> (i64, u32) {
+    if unit.as_secs() == 0 {
+        let n = unit.subsec_nanos();
+        return (local, nano - nano % n);
+    }
+
+    (local - local.rem_euclid(unit.as_secs() as i64), 0)
 }
 
+#[allow(clippy::manual_is_multiple_of)]
 fn is_leap_year(y: u32) -> bool {
-    return y % 4 == 0 && (y % 100 != 0 || y % 400 == 0);
+    y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)
 }
 
 fn in_range(n: u32, min: u32, max: u32) -> bool {
-    return min <= n && n <= max;
+    (min..=max).contains(&n)
 }
 
 fn is_day_validate(is_leap: bool, m: u32, d: u32) -> bool {
@@ -207,6 +442,15 @@ impl FromStr for Time {
     }
 }
 
+impl Default for Time {
+    /// Returns [`Time::UNIX_EPOCH`]
+    ///
+    /// [`Time::UNIX_EPOCH`]: #associatedconstant.UNIX_EPOCH
+    fn default() -> Time {
+        UNIX_EPOCH
+    }
+}
+
 impl PartialOrd for Time {
     fn partial_cmp(&self, other: &Time) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -223,11 +467,240 @@ impl Ord for Time {
     }
 }
 
-/// Parses a [`RFC3339`] datetime string
+/// Policy of parsing a leap second, (e.g. "2016-12-31T23:59:60Z")
+///
+/// As in [`RFC3339`] section 5.6, the second 60 is only valid as the last second of a UTC day,
+/// it is rejected at any other time regardless of the policy.
+///
+/// [`RFC3339`]: https://tools.ietf.org/html/rfc3339#section-5.6
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum LeapSecond {
+    /// Rejects leap seconds
+    Reject,
+
+    /// Clamps a leap second to the end of the previous second, (e.g. "23:59:59.999999999Z"),
+    /// which keeps the order of times within the day
+    #[default]
+    Clamp,
+
+    /// Rolls a leap second into the next second, (e.g. "23:59:60.5Z" to "00:00:00.5Z" of the next day)
+    Roll,
+}
+
+impl LeapSecond {
+    // applies the leap second policy to the time parsed with the second 59
+    fn apply(&self, t: Time) -> Result<Time, ParseError> {
+        if t.sec.rem_euclid(SECS_PER_DAY as i64) != SECS_PER_DAY as i64 - 1 {
+            return Err(ParseError::Overflow);
+        }
+
+        match *self {
+            LeapSecond::Reject => Err(ParseError::Overflow),
+            LeapSecond::Clamp => Ok(Time {
+                nano: NANOS_PER_SEC as u32 - 1,
+                ..t
+            }),
+            LeapSecond::Roll => t
+                .checked_add(Duration::from_secs(1))
+                .ok_or(ParseError::Overflow),
+        }
+    }
+}
+
+/// Conformance of the datetime parser
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Mode {
+    /// Accepts exactly the `date-time` of the [`RFC3339`] ABNF,
+    /// (e.g. "2018-09-21T16:56:44.234Z", "2018-09-21t16:56:44+08:30")
+    ///
+    /// [`RFC3339`]: https://tools.ietf.org/html/rfc3339#section-5.6
+    Strict,
+
+    /// Additionally accepts common ISO 8601 variants:
+    /// - date only, (e.g. "2018-09-21"), and a space separator, (e.g. "2018-09-21 16:56:44")
+    /// - time without seconds, (e.g. "2018-09-21T16:56Z")
+    /// - missing offset, which means UTC, or a zone given to [`Rfc3339Parser::parse_in`]
+    /// - the basic format, (e.g. "20180921T165644Z")
+    /// - offsets "±hhmm" and "±hh", (e.g. "+0800", "+08")
+    /// - a comma as the decimal sign, (e.g. "16:56:44,234")
+    /// - expanded years of a sign and 4 to 6 digits, (e.g. "+012345-01-01", "-0044-03-15T12:00Z")
+    ///
+    /// [`Rfc3339Parser::parse_in`]: ./struct.Rfc3339Parser.html#method.parse_in
+    #[default]
+    Lenient,
+}
+
+/// [`RFC3339`] datetime parser with options
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{parse_rfc3339, LeapSecond, Mode, Rfc3339Parser};
+///
+/// let s = "2016-12-31T23:59:60Z";
+/// assert_eq!(
+///     Rfc3339Parser::new().parse(s),
+///     parse_rfc3339("2016-12-31T23:59:59.999999999Z")
+/// );
+/// assert_eq!(
+///     Rfc3339Parser::new().leap_second(LeapSecond::Roll).parse(s),
+///     parse_rfc3339("2017-01-01T00:00:00Z")
+/// );
+/// assert!(Rfc3339Parser::new().leap_second(LeapSecond::Reject).parse(s).is_err());
+///
+/// let strict = Rfc3339Parser::new().mode(Mode::Strict);
+/// assert!(strict.parse("2018-09-21 16:56:44").is_err());
+/// assert_eq!(
+///     strict.parse("2018-09-21t08:56:44z"),
+///     Rfc3339Parser::new().parse("20180921T165644+0800")
+/// );
+/// ```
+///
+/// [`RFC3339`]: https://tools.ietf.org/html/rfc3339
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Rfc3339Parser {
+    mode: Mode,
+    leap_second: LeapSecond,
+}
+
+impl Rfc3339Parser {
+    /// Returns a lenient parser which clamps leap seconds
+    pub fn new() -> Rfc3339Parser {
+        Rfc3339Parser::default()
+    }
+
+    /// Sets the conformance mode
+    pub fn mode(mut self, mode: Mode) -> Rfc3339Parser {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the policy of parsing leap seconds
+    pub fn leap_second(mut self, policy: LeapSecond) -> Rfc3339Parser {
+        self.leap_second = policy;
+        self
+    }
+
+    /// Parses a datetime string
+    pub fn parse(&self, s: &str) -> Result<Time, ParseError> {
+        let (t, _, leap) = parse(s, self)?;
+        if leap {
+            return self.leap_second.apply(t);
+        }
+
+        Ok(t)
+    }
+
+    /// Parses a datetime string, reading one without offset as the wall clock time in `zone`,
+    /// which is resolved by `disambiguation` if it is skipped or repeated there.
+    ///
+    /// Returns `ParseError::InvalidValue` if the wall clock time is rejected,
+    /// and `ParseError::Overflow` if it is out of range.
+    ///
+    /// # Example
+    /// ```
+    /// use humanize_rs::time::{parse_rfc3339, Disambiguation, PosixTz, Rfc3339Parser};
+    ///
+    /// let zone = "CST6CDT,M3.2.0,M11.1.0".parse::<PosixTz>().unwrap();
+    /// let p = Rfc3339Parser::new();
+    /// assert_eq!(
+    ///     p.parse_in("2018-09-21 16:56:44", &zone, Disambiguation::Compatible),
+    ///     parse_rfc3339("2018-09-21T16:56:44-05:00")
+    /// );
+    /// assert_eq!(
+    ///     p.parse_in("2018-09-21 16:56:44Z", &zone, Disambiguation::Compatible),
+    ///     parse_rfc3339("2018-09-21T16:56:44Z")
+    /// );
+    /// ```
+    pub fn parse_in<Z: Zone + ?Sized>(
+        &self,
+        s: &str,
+        zone: &Z,
+        disambiguation: Disambiguation,
+    ) -> Result<Time, ParseError> {
+        let (t, has_offset, leap) = parse(s, self)?;
+        let t = if has_offset {
+            t
+        } else {
+            match zone.local(&t) {
+                LocalResult::None => return Err(ParseError::Overflow),
+                _ => zone
+                    .resolve(&t, disambiguation)
+                    .ok_or(ParseError::InvalidValue)?,
+            }
+        };
+
+        // the leap second is at the end of the UTC day, so it is checked after the zone is resolved
+        if leap {
+            return self.leap_second.apply(t);
+        }
+
+        Ok(t)
+    }
+}
+
+/// Parses a [`RFC3339`] datetime string in the lenient mode, leap seconds are clamped,
+/// see [`Rfc3339Parser`] for more options.
 ///
 /// [`RFC3339`]: https://tools.ietf.org/html/rfc3339
+/// [`Rfc3339Parser`]: ./struct.Rfc3339Parser.html
 pub fn parse_rfc3339(s: &str) -> Result<Time, ParseError> {
-    let bs = s.trim().as_bytes();
+    Rfc3339Parser::new().parse(s)
+}
+
+/// Formats the time as a [`RFC3339`] datetime string in the given zone,
+/// (e.g. "2018-09-21T16:56:44.234+08:00"), the fraction of second is written without
+/// trailing zeros, and UTC is written as "Z".
+///
+/// The offset is the one of the zone at the time, it is written by the `Display` of `TimeZone`, which drops its seconds,
+/// and the local time is in the offset of whole minutes, so that the string is still the same time.
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{format_rfc3339, parse_rfc3339, TimeZone};
+///
+/// let t = parse_rfc3339("2018-09-21T08:56:44.234Z").unwrap();
+/// assert_eq!(
+///     format_rfc3339(&t, TimeZone::new(8).unwrap()),
+///     "2018-09-21T16:56:44.234+08:00"
+/// );
+/// assert_eq!(format_rfc3339(&t, TimeZone::utc()), "2018-09-21T08:56:44.234Z");
+/// ```
+///
+/// [`RFC3339`]: https://tools.ietf.org/html/rfc3339
+pub fn format_rfc3339<Z: Zone>(t: &Time, zone: Z) -> String {
+    let timezone = zone.offset_at(t).whole_minutes();
+    format!(
+        "{}T{}{}",
+        t.to_date(timezone),
+        t.time_of_day(timezone),
+        timezone
+    )
+}
+
+// returns the time, whether it has an offset, and whether it is a leap second,
+// which is read as the second 59 for the policy to be applied by the caller
+fn parse(s: &str, parser: &Rfc3339Parser) -> Result<(Time, bool, bool), ParseError> {
+    let lenient = parser.mode == Mode::Lenient;
+    let mut bs = s.trim().as_bytes();
+
+    // an expanded year is replaced by 4 digits, so that the fields are at fixed positions
+    let expanded = if lenient {
+        read_expanded_year(bs)?
+    } else {
+        None
+    };
+    let placeholder;
+    if let Some((_, n)) = expanded {
+        placeholder = [b"0000", &bs[n..]].concat();
+        bs = &placeholder[..];
+    }
+
+    let normalized;
+    if lenient {
+        normalized = normalize(bs);
+        bs = &normalized[..];
+    }
+
     let size = bs.len();
     if size == 0 {
         return Err(ParseError::EmptyInput);
@@ -235,6 +708,7 @@ pub fn parse_rfc3339(s: &str) -> Result<Time, ParseError> {
 
     if size < DATE_TIME_FORMAT_MIN_LENGTH
         || (size > DATE_TIME_FORMAT_MIN_LENGTH && size < DATE_TIME_FORMAT_WITH_TIME)
+        || (!lenient && size <= DATE_TIME_FORMAT_WITH_TIME)
     {
         return Err(ParseError::TooShort);
     }
@@ -243,11 +717,14 @@ pub fn parse_rfc3339(s: &str) -> Result<Time, ParseError> {
         return Err(ParseError::TooLong);
     }
 
-    if !check_pattern(bs) {
+    if !check_pattern(bs, lenient) {
         return Err(ParseError::Malformed);
     }
 
-    let year = read_u32(&bs[0..4])?;
+    let year = match expanded {
+        Some((year, _)) => year,
+        None => read_u32(&bs[0..4])? as i64,
+    };
     let month = read_u32(&bs[5..7])?;
     let day = read_u32(&bs[8..10])?;
 
@@ -265,7 +742,7 @@ pub fn parse_rfc3339(s: &str) -> Result<Time, ParseError> {
     }
 
     let nano: u32;
-    let tzstr: &str;
+    let tzbs: &[u8];
     if size > DATE_TIME_FORMAT_WITH_TIME {
         let tz_start: usize;
         if bs[DATE_TIME_FORMAT_WITH_TIME] == b'.' {
@@ -280,25 +757,88 @@ pub fn parse_rfc3339(s: &str) -> Result<Time, ParseError> {
             tz_start = DATE_TIME_FORMAT_WITH_TIME;
         }
 
-        tzstr = from_utf8(&bs[tz_start..]).or(Err(ParseError::InvalidTimezone))?;
+        tzbs = &bs[tz_start..];
     } else {
         nano = 0;
-        tzstr = "";
+        tzbs = b"";
+    }
+
+    // the offset is required by RFC3339
+    if !lenient && tzbs.is_empty() {
+        return Err(ParseError::InvalidTimezone);
+    }
+
+    let tz = parse_offset(tzbs, lenient)?;
+    let has_offset = !tzbs.is_empty();
+
+    let date = Date::new(year as i32, month, day).ok_or(ParseError::Overflow)?;
+    let time_of_day = |second| {
+        TimeOfDay::new(hour, minute, second, nano)
+            .and_then(|time| date.at(time, tz))
+            .ok_or(ParseError::Overflow)
+    };
+
+    if second == 60 {
+        return time_of_day(59).map(|t| (t, has_offset, true));
+    }
+
+    time_of_day(second).map(|t| (t, has_offset, false))
+}
+
+// rewrites ISO 8601 variants into the extended format,
+// (e.g. "20180921T1656,5+0800" to "2018-09-21T16:56:00.5+0800")
+fn normalize(bs: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(DATE_TIME_FORMAT_MAX_LENGTH);
+    let mut rest = bs;
+
+    // basic format, "YYYYMMDD" with optional "Thhmm[ss]"
+    let is_digits = |bs: &[u8]| bs.iter().all(u8::is_ascii_digit);
+    if bs.len() >= 8 && is_digits(&bs[..8]) {
+        out.extend_from_slice(&bs[0..4]);
+        out.push(b'-');
+        out.extend_from_slice(&bs[4..6]);
+        out.push(b'-');
+        out.extend_from_slice(&bs[6..8]);
+        rest = &bs[8..];
+
+        if rest.len() >= 5 && is_digits(&rest[1..5]) {
+            out.push(rest[0]);
+            out.extend_from_slice(&rest[1..3]);
+            out.push(b':');
+            out.extend_from_slice(&rest[3..5]);
+            rest = &rest[5..];
+
+            if rest.len() >= 2 && is_digits(&rest[..2]) {
+                out.push(b':');
+                out.extend_from_slice(&rest[..2]);
+                rest = &rest[2..];
+            }
+        }
     }
+    out.extend_from_slice(rest);
 
-    let tz = tzstr.parse::<TimeZone>()?;
+    // reduced precision, "hh:mm" without seconds
+    let i = DATE_TIME_FORMAT_MIN_LENGTH + 6;
+    if out.len() >= i && out[DATE_TIME_FORMAT_MIN_LENGTH + 3] == b':' && out.get(i) != Some(&b':') {
+        out.splice(i..i, b":00".iter().cloned());
+    }
+
+    // comma as the decimal sign
+    if out.get(DATE_TIME_FORMAT_WITH_TIME) == Some(&b',') {
+        out[DATE_TIME_FORMAT_WITH_TIME] = b'.';
+    }
 
-    Time::from_timetuple(year, month, day, hour, minute, second, nano, tz)
-        .ok_or(ParseError::Overflow)
+    out
 }
 
-fn check_pattern(bs: &[u8]) -> bool {
+fn check_pattern(bs: &[u8], lenient: bool) -> bool {
     if bs[4] != b'-' || bs[7] != b'-' {
         return false;
     }
 
     if bs.len() > DATE_TIME_FORMAT_MIN_LENGTH {
-        if (bs[DATE_TIME_FORMAT_MIN_LENGTH] != b'T' && bs[DATE_TIME_FORMAT_MIN_LENGTH] != b' ')
+        let sep = bs[DATE_TIME_FORMAT_MIN_LENGTH];
+        if !(sep == b'T' || sep == b't' || (lenient && sep == b' '))
             || bs[DATE_TIME_FORMAT_MIN_LENGTH + 3] != b':'
             || bs[DATE_TIME_FORMAT_MIN_LENGTH + 6] != b':'
         {
@@ -307,12 +847,9 @@ fn check_pattern(bs: &[u8]) -> bool {
     }
 
     if bs.len() > DATE_TIME_FORMAT_WITH_TIME {
-        if bs[DATE_TIME_FORMAT_WITH_TIME] != b'.'
-            && bs[DATE_TIME_FORMAT_WITH_TIME] != b'Z'
-            && bs[DATE_TIME_FORMAT_WITH_TIME] != b'+'
-            && bs[DATE_TIME_FORMAT_WITH_TIME] != b'-'
-        {
-            return false;
+        match bs[DATE_TIME_FORMAT_WITH_TIME] {
+            b'.' | b'Z' | b'z' | b'+' | b'-' => {}
+            _ => return false,
         }
     }
 
@@ -325,11 +862,11 @@ fn read_u32(bs: &[u8]) -> Result<u32, ParseError> {
 
     while read < bs.len() {
         let c = bs[read];
-        if c < b'0' || c > b'9' {
+        if !c.is_ascii_digit() {
             return Err(ParseError::InvalidValue);
         }
 
-        n = n * 10;
+        n *= 10;
         n += (c - b'0') as u32;
 
         read += 1;
@@ -338,24 +875,45 @@ fn read_u32(bs: &[u8]) -> Result<u32, ParseError> {
     Ok(n)
 }
 
+// reads an ISO 8601 expanded year, a sign and 4 to 6 digits followed by "-",
+// (e.g. "+012345-", "-0044-"), returns the year and the length of it
+fn read_expanded_year(bs: &[u8]) -> Result<Option<(i64, usize)>, ParseError> {
+    let neg = match bs.first() {
+        Some(b'+') => false,
+        Some(b'-') => true,
+        _ => return Ok(None),
+    };
+
+    let n = bs[1..].iter().take_while(|c| c.is_ascii_digit()).count();
+    if !(4..=6).contains(&n) || bs.get(n + 1) != Some(&b'-') {
+        return Err(ParseError::Malformed);
+    }
+
+    let year = read_u32(&bs[1..=n])? as i64;
+    Ok(Some((if neg { -year } else { year }, n + 1)))
+}
+
 fn read_nano(bs: &[u8]) -> (u32, usize) {
     let mut read: usize = 0;
     let mut n: u32 = 0;
 
-    while read < bs.len() && read <= 9 {
+    while read < bs.len() {
         let c = bs[read];
-        if c < b'0' || c > b'9' {
+        if !c.is_ascii_digit() {
             break;
         }
 
-        n = n * 10;
-        n += (c - b'0') as u32;
+        // digits beyond nanoseconds are truncated
+        if read < 9 {
+            n *= 10;
+            n += (c - b'0') as u32;
+        }
 
         read += 1;
     }
 
     if read < 9 {
-        n = n * 10_u32.pow((9 - read) as u32);
+        n *= 10_u32.pow((9 - read) as u32);
     }
 
     (n, read)
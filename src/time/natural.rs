@@ -0,0 +1,141 @@
+use super::{Time, Zone};
+use duration::{self, English, Unit};
+use std::str::FromStr;
+use std::time::Duration;
+use ParseError;
+
+/// Parses a natural-language time relative to `reference`,
+/// calendar days start at midnight in the given zone, see [`Time::truncate`].
+///
+/// Accepted forms, case-insensitive:
+/// - "now", "today", "yesterday", "tomorrow"
+/// - "N units ago", "in N units", with several components allowed, (e.g. "in 1 hour 30 minutes")
+/// - "now-15m", "now+1h", the offset being a duration accepted by [`duration::parse`]
+///
+/// Units are either the duration symbols ("s", "m", "h", "d", ...) or their English names,
+/// (e.g. "second", "minutes"), and "a" or "an" may be used for one, (e.g. "an hour ago").
+///
+/// [`duration::parse`]: ../duration/fn.parse.html
+/// [`Time::truncate`]: ./struct.Time.html#method.truncate
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{parse_relative, Time, TimeZone};
+///
+/// let now = "2018-09-21T16:56:44Z".parse::<Time>().unwrap();
+/// let utc = TimeZone::utc();
+///
+/// assert_eq!(
+///     parse_relative("3 days ago", &now, utc),
+///     "2018-09-18T16:56:44Z".parse::<Time>()
+/// );
+/// assert_eq!(
+///     parse_relative("in 2 hours", &now, utc),
+///     "2018-09-21T18:56:44Z".parse::<Time>()
+/// );
+/// assert_eq!(
+///     parse_relative("yesterday", &now, utc),
+///     "2018-09-20T00:00:00Z".parse::<Time>()
+/// );
+/// assert_eq!(
+///     parse_relative("now-15m", &now, utc),
+///     "2018-09-21T16:41:44Z".parse::<Time>()
+/// );
+/// ```
+pub fn parse_relative<Z: Zone>(s: &str, reference: &Time, zone: Z) -> Result<Time, ParseError> {
+    let input = s.trim().to_lowercase();
+    if input.is_empty() {
+        return Err(ParseError::EmptyInput);
+    }
+
+    // a day is not always 24 hours long in a zone with daylight saving time
+    let midnight = |days: i64| {
+        reference
+            .checked_add_days(days, &zone)
+            .and_then(|t| t.truncate(Unit::Day, &zone))
+    };
+    let t = match input.as_str() {
+        "now" => Some(*reference),
+        "today" => midnight(0),
+        "yesterday" => midnight(-1),
+        "tomorrow" => midnight(1),
+        _ => return parse_offset(&input, reference),
+    };
+
+    t.ok_or(ParseError::Overflow)
+}
+
+// parses "now+<duration>", "N <unit> ago" and "in N <unit>"
+fn parse_offset(input: &str, reference: &Time) -> Result<Time, ParseError> {
+    if let Some(offset) = input.strip_prefix("now") {
+        let offset = offset.trim_start();
+        let (neg, d) = match offset.as_bytes().first() {
+            Some(b'+') => (false, duration::parse(offset[1..].trim_start())?),
+            Some(b'-') => (true, duration::parse(offset[1..].trim_start())?),
+            _ => return Err(ParseError::Malformed),
+        };
+
+        return shift(reference, d, neg);
+    }
+
+    if let Some(span) = input.strip_suffix(" ago") {
+        let d = parse_span(span)?;
+        return shift(reference, d, true);
+    }
+
+    if let Some(span) = input.strip_prefix("in ") {
+        let d = parse_span(span)?;
+        return shift(reference, d, false);
+    }
+
+    Err(ParseError::Malformed)
+}
+
+fn shift(reference: &Time, d: Duration, neg: bool) -> Result<Time, ParseError> {
+    let t = if neg {
+        reference.checked_sub(d)
+    } else {
+        reference.checked_add(d)
+    };
+
+    t.ok_or(ParseError::Overflow)
+}
+
+// parses a sequence of "N <unit>" components, (e.g. "2 hours 30 minutes", "2h 30m")
+fn parse_span(s: &str) -> Result<Duration, ParseError> {
+    let mut words = s.split_whitespace();
+    let mut total = Duration::new(0, 0);
+    let mut empty = true;
+
+    while let Some(word) = words.next() {
+        empty = false;
+        let i = word
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(word.len());
+
+        let (count, unit) = match (&word[..i], &word[i..]) {
+            ("", "a") | ("", "an") => (1, words.next()),
+            ("", _) => return Err(ParseError::MissingValue),
+            (n, "") => (
+                n.parse::<u64>().map_err(|_| ParseError::Overflow)?,
+                words.next(),
+            ),
+            (n, u) => (n.parse::<u64>().map_err(|_| ParseError::Overflow)?, Some(u)),
+        };
+
+        let unit = unit.ok_or(ParseError::MissingUnit)?;
+        let unit = Unit::from_str(unit)
+            .ok()
+            .or_else(|| English::parse_unit_name(unit))
+            .ok_or(ParseError::InvalidUnit)?;
+
+        let d = unit.times(count).ok_or(ParseError::Overflow)?;
+        total = total.checked_add(d).ok_or(ParseError::Overflow)?;
+    }
+
+    if empty {
+        return Err(ParseError::MissingValue);
+    }
+
+    Ok(total)
+}
@@ -0,0 +1,378 @@
+use super::{days_from_civil, is_leap_year, Time, TimeZone, Zone, SECS_PER_DAY};
+use std::str::FromStr;
+use ParseError;
+
+// the rule used for a daylight saving time without one, which is the US rule since 2007
+const DEFAULT_RULE: &str = "M3.2.0,M11.1.0";
+// the default time of day of a transition
+const DEFAULT_RULE_TIME: i32 = 2 * 3600;
+// the hour bounds of transition times, extended by RFC8536 from POSIX's 0 to 24
+const MAX_RULE_HOURS: u32 = 167;
+
+/// A time zone of a [`POSIX TZ`] string, (e.g. "CST6CDT,M3.2.0,M11.1.0"),
+/// as set in the environment variable `TZ` of many embedded systems
+///
+/// The string is `std offset [dst [offset] [,start[/time],end[/time]]]`:
+/// - `std` and `dst` are names of 3 or more letters, or quoted like "<+08>"
+/// - `offset` is `[±]hh[:mm[:ss]]` **west** of UTC, the one of `dst` defaults to an hour less
+/// - `start` and `end` of daylight saving time are one of `Jn`, the n-th day of year from 1 to 365
+///   not counting February 29, `n`, the day of year from 0 to 365 counting it,
+///   and `Mm.w.d`, the day `d` (0 is Sunday) of week `w` (5 is the last) of month `m`
+/// - `time` is the local time of day of the transition, 02:00:00 by default,
+///   and may be negative or up to 167 hours as extended by [`RFC8536`]
+///
+/// A daylight saving time without rules follows the US rule "M3.2.0,M11.1.0".
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{format_rfc3339, parse_rfc3339, PosixTz, Zone};
+///
+/// let tz = "CST6CDT,M3.2.0,M11.1.0".parse::<PosixTz>().unwrap();
+///
+/// let t = parse_rfc3339("2018-07-01T12:00:00Z").unwrap();
+/// assert_eq!(tz.offset_at(&t).offset(), -5 * 3600);
+/// assert_eq!(tz.abbreviation_at(&t), "CDT");
+///
+/// let t = parse_rfc3339("2018-12-01T12:00:00Z").unwrap();
+/// assert_eq!(tz.offset_at(&t).offset(), -6 * 3600);
+/// assert_eq!(tz.abbreviation_at(&t), "CST");
+/// assert_eq!(format_rfc3339(&t, &tz), "2018-12-01T06:00:00-06:00");
+/// ```
+///
+/// [`POSIX TZ`]: https://pubs.opengroup.org/onlinepubs/9699919799/basedefs/V1_chap08.html
+/// [`RFC8536`]: https://tools.ietf.org/html/rfc8536#section-3.3.1
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PosixTz {
+    std_name: String,
+    std_offset: TimeZone,
+    dst: Option<Dst>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Dst {
+    name: String,
+    offset: TimeZone,
+    start: Rule,
+    end: Rule,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Rule {
+    date: RuleDate,
+    // local time of day in seconds
+    time: i32,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum RuleDate {
+    // day of year from 1 to 365, February 29 is never counted
+    Julian(u32),
+    // day of year from 0 to 365, February 29 is counted in leap years
+    Ordinal(u32),
+    // day of week (0 is Sunday) of week 1 to 5 (the last) of month
+    Weekday { month: u32, week: u32, day: u32 },
+}
+
+impl PosixTz {
+    /// Parses a POSIX TZ string
+    pub fn new(s: &str) -> Result<PosixTz, ParseError> {
+        if s.is_empty() {
+            return Err(ParseError::EmptyInput);
+        }
+
+        let mut r = Reader { bs: s.as_bytes() };
+        let std_name = r.name()?;
+        let std_offset = r.offset()?;
+
+        let dst = if r.bs.is_empty() {
+            None
+        } else {
+            let name = r.name()?;
+            let offset = match r.bs.first() {
+                Some(b',') | None => TimeZone::from_offset(std_offset.offset() + 3600)
+                    .ok_or(ParseError::InvalidTimezone)?,
+                _ => r.offset()?,
+            };
+
+            let (start, end) = if r.bs.is_empty() {
+                Reader {
+                    bs: DEFAULT_RULE.as_bytes(),
+                }
+                .rules()?
+            } else {
+                r.expect(b',')?;
+                r.rules()?
+            };
+
+            Some(Dst {
+                name,
+                offset,
+                start,
+                end,
+            })
+        };
+
+        if !r.bs.is_empty() {
+            return Err(ParseError::Malformed);
+        }
+
+        Ok(PosixTz {
+            std_name,
+            std_offset,
+            dst,
+        })
+    }
+
+    /// Returns the abbreviation of the local time at the time, (e.g. "CST", "CDT")
+    pub fn abbreviation_at(&self, t: &Time) -> &str {
+        match self.dst_at(t) {
+            Some(dst) => &dst.name,
+            None => &self.std_name,
+        }
+    }
+
+    /// Returns whether daylight saving time is in effect at the time
+    pub fn is_dst_at(&self, t: &Time) -> bool {
+        self.dst_at(t).is_some()
+    }
+
+    fn dst_at(&self, t: &Time) -> Option<&Dst> {
+        let dst = self.dst.as_ref()?;
+        let (year, _, _) = t.date(self.std_offset);
+        let ts = t.sec;
+
+        // the transitions are given in the local time before them
+        let start = dst.start.local_seconds(year) - self.std_offset.offset() as i64;
+        let end = dst.end.local_seconds(year) - dst.offset.offset() as i64;
+
+        let in_dst = if start <= end {
+            start <= ts && ts < end
+        } else {
+            // daylight saving time across the new year, as in the southern hemisphere
+            ts < end || start <= ts
+        };
+
+        if in_dst {
+            Some(dst)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for PosixTz {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PosixTz::new(s)
+    }
+}
+
+impl Zone for PosixTz {
+    fn offset_at(&self, t: &Time) -> TimeZone {
+        match self.dst_at(t) {
+            Some(dst) => dst.offset,
+            None => self.std_offset,
+        }
+    }
+}
+
+impl Rule {
+    // returns the local seconds since 0000-01-01 of the transition in the year
+    fn local_seconds(&self, year: i64) -> i64 {
+        let jan1 = days_from_civil(year, 1, 1);
+        let leap = is_leap_year(year.rem_euclid(400) as u32);
+
+        let days = match self.date {
+            RuleDate::Julian(n) if leap && n >= 60 => jan1 + n as i64,
+            RuleDate::Julian(n) => jan1 + n as i64 - 1,
+            RuleDate::Ordinal(n) => jan1 + n as i64,
+            RuleDate::Weekday { month, week, day } => {
+                let first = days_from_civil(year, month, 1);
+                let next = match month {
+                    12 => days_from_civil(year + 1, 1, 1),
+                    _ => days_from_civil(year, month + 1, 1),
+                };
+
+                // 0000-01-01 is a Saturday, the 6th day counted from Sunday
+                let weekday = (first + 6).rem_euclid(7);
+                let mut days = first + (day as i64 - weekday).rem_euclid(7) + (week as i64 - 1) * 7;
+                while days >= next {
+                    days -= 7;
+                }
+                days
+            }
+        };
+
+        days * SECS_PER_DAY as i64 + self.time as i64
+    }
+}
+
+struct Reader<'a> {
+    bs: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn expect(&mut self, c: u8) -> Result<(), ParseError> {
+        match self.bs.split_first() {
+            Some((&first, rest)) if first == c => {
+                self.bs = rest;
+                Ok(())
+            }
+            _ => Err(ParseError::Malformed),
+        }
+    }
+
+    // reads a name of 3 or more letters, or a quoted one like "<+0330>"
+    fn name(&mut self) -> Result<String, ParseError> {
+        let (name, rest) = if self.bs.first() == Some(&b'<') {
+            let end = self
+                .bs
+                .iter()
+                .position(|&c| c == b'>')
+                .ok_or(ParseError::Malformed)?;
+            let name = &self.bs[1..end];
+            if !name
+                .iter()
+                .all(|&c| c.is_ascii_alphanumeric() || c == b'+' || c == b'-')
+            {
+                return Err(ParseError::Malformed);
+            }
+            (name, &self.bs[end + 1..])
+        } else {
+            let n = self
+                .bs
+                .iter()
+                .take_while(|c| c.is_ascii_alphabetic())
+                .count();
+            self.bs.split_at(n)
+        };
+
+        if name.len() < 3 {
+            return Err(ParseError::Malformed);
+        }
+
+        self.bs = rest;
+        Ok(String::from_utf8_lossy(name).into_owned())
+    }
+
+    // reads an offset west of UTC, returning it as a timezone east of UTC
+    fn offset(&mut self) -> Result<TimeZone, ParseError> {
+        if self.bs.is_empty() {
+            return Err(ParseError::MissingValue);
+        }
+
+        let secs = self.clock(24)?;
+        TimeZone::from_offset(-secs).ok_or(ParseError::InvalidTimezone)
+    }
+
+    // reads a signed "hh[:mm[:ss]]" in seconds, the hour is at most max_hours
+    fn clock(&mut self, max_hours: u32) -> Result<i32, ParseError> {
+        let sign = match self.bs.first() {
+            Some(b'-') => -1,
+            Some(b'+') => 1,
+            _ => 0,
+        };
+        if sign != 0 {
+            self.bs = &self.bs[1..];
+        }
+
+        let hours = self.number(1, 3)?;
+        if hours > max_hours {
+            return Err(ParseError::InvalidValue);
+        }
+
+        let mut secs = hours * 3600;
+        for &unit in [60, 1].iter() {
+            if self.bs.first() != Some(&b':') {
+                break;
+            }
+            self.bs = &self.bs[1..];
+
+            let n = self.number(2, 2)?;
+            if n > 59 {
+                return Err(ParseError::InvalidValue);
+            }
+            secs += n * unit;
+        }
+
+        Ok(if sign < 0 {
+            -(secs as i32)
+        } else {
+            secs as i32
+        })
+    }
+
+    // reads the start and end rules of daylight saving time, "start[/time],end[/time]"
+    fn rules(&mut self) -> Result<(Rule, Rule), ParseError> {
+        let start = self.rule()?;
+        self.expect(b',')?;
+        Ok((start, self.rule()?))
+    }
+
+    // reads a transition rule "date[/time]"
+    fn rule(&mut self) -> Result<Rule, ParseError> {
+        let date = match self.bs.first() {
+            Some(b'J') => {
+                self.bs = &self.bs[1..];
+                RuleDate::Julian(self.number_in(1, 3, 1, 365)?)
+            }
+            Some(b'M') => {
+                self.bs = &self.bs[1..];
+                let month = self.number_in(1, 2, 1, 12)?;
+                self.expect(b'.')?;
+                let week = self.number_in(1, 1, 1, 5)?;
+                self.expect(b'.')?;
+                let day = self.number_in(1, 1, 0, 6)?;
+                RuleDate::Weekday { month, week, day }
+            }
+            _ => RuleDate::Ordinal(self.number_in(1, 3, 0, 365)?),
+        };
+
+        let time = if self.bs.first() == Some(&b'/') {
+            self.bs = &self.bs[1..];
+            self.clock(MAX_RULE_HOURS)?
+        } else {
+            DEFAULT_RULE_TIME
+        };
+
+        if !self.bs.is_empty() && self.bs[0] != b',' {
+            return Err(ParseError::Malformed);
+        }
+
+        Ok(Rule { date, time })
+    }
+
+    // reads a number of min to max digits
+    fn number(&mut self, min: usize, max: usize) -> Result<u32, ParseError> {
+        let n = self.bs.iter().take_while(|c| c.is_ascii_digit()).count();
+        if n == 0 {
+            return Err(ParseError::MissingValue);
+        }
+
+        if n < min || n > max {
+            return Err(ParseError::InvalidValue);
+        }
+
+        let (digits, rest) = self.bs.split_at(n);
+        self.bs = rest;
+        Ok(digits
+            .iter()
+            .fold(0, |acc, &c| acc * 10 + u32::from(c - b'0')))
+    }
+
+    fn number_in(
+        &mut self,
+        min_digits: usize,
+        max_digits: usize,
+        min: u32,
+        max: u32,
+    ) -> Result<u32, ParseError> {
+        let n = self.number(min_digits, max_digits)?;
+        if n < min || n > max {
+            return Err(ParseError::InvalidValue);
+        }
+
+        Ok(n)
+    }
+}
@@ -0,0 +1,150 @@
+use super::format::{Fields, Format};
+use super::{Disambiguation, LeapSecond, Mode, Rfc3339Parser, Time, Zone};
+use ParseError;
+
+const CLF_FORMAT: &str = "%d/%b/%Y:%H:%M:%S %z";
+const SYSLOG_FORMAT: &str = "%b %e %T";
+const LOG4J_FORMATS: [&str; 2] = ["%F %T,%3f", "%FT%T,%3f"];
+
+/// Parses a timestamp of the Common Log Format used by Apache and Nginx,
+/// (e.g. "10/Oct/2000:13:55:36 -0700"), the surrounding brackets are optional.
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{parse_clf, parse_rfc3339};
+///
+/// assert_eq!(
+///     parse_clf("[10/Oct/2000:13:55:36 -0700]"),
+///     parse_rfc3339("2000-10-10T13:55:36-07:00")
+/// );
+/// ```
+pub fn parse_clf(s: &str) -> Result<Time, ParseError> {
+    let s = s.trim();
+    let s = match (s.starts_with('['), s.ends_with(']')) {
+        (true, true) if s.len() > 1 => &s[1..s.len() - 1],
+        (false, false) => s,
+        _ => return Err(ParseError::Malformed),
+    };
+
+    Format::new(CLF_FORMAT)?.parse(s)
+}
+
+/// Parses a timestamp of the classic BSD syslog, [`RFC3164`], (e.g. "Oct 11 22:14:15"),
+/// in the given zone, a time skipped or repeated there is resolved by `Disambiguation::Compatible`.
+///
+/// As the year is missing, it is inferred as the one that puts the time closest to `reference`,
+/// which is usually the time the message is received, (e.g. "Dec 31 23:59:59" received at
+/// 2019-01-01T00:00:01Z is in 2018).
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{parse_rfc3339, parse_syslog, TimeZone};
+///
+/// let received = parse_rfc3339("2019-01-01T00:00:01Z").unwrap();
+/// assert_eq!(
+///     parse_syslog("Dec 31 23:59:59", &received, TimeZone::utc()),
+///     parse_rfc3339("2018-12-31T23:59:59Z")
+/// );
+/// assert_eq!(
+///     parse_syslog("Jan  1 00:00:00", &received, TimeZone::utc()),
+///     parse_rfc3339("2019-01-01T00:00:00Z")
+/// );
+/// ```
+///
+/// [`RFC3164`]: https://tools.ietf.org/html/rfc3164#section-4.1.2
+pub fn parse_syslog<Z: Zone>(s: &str, reference: &Time, zone: Z) -> Result<Time, ParseError> {
+    let fields = Format::new(SYSLOG_FORMAT)?.parse_fields(s.trim())?;
+    infer_year(fields, reference, &zone)
+}
+
+/// Parses a timestamp of the [`RFC5424`] syslog protocol, (e.g. "2018-09-21T16:56:44.234+08:00"),
+/// which is a RFC3339 datetime with an uppercase "T" and "Z", at most 6 digits of fraction
+/// and no leap second. Returns `ParseError::MissingValue` for the NILVALUE "-".
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{parse_rfc3339, parse_rfc5424};
+///
+/// assert_eq!(
+///     parse_rfc5424("2003-10-11T22:14:15.003Z"),
+///     parse_rfc3339("2003-10-11T22:14:15.003Z")
+/// );
+/// assert!(parse_rfc5424("2003-10-11T22:14:15.000000003Z").is_err());
+/// ```
+///
+/// [`RFC5424`]: https://tools.ietf.org/html/rfc5424#section-6.2.3
+pub fn parse_rfc5424(s: &str) -> Result<Time, ParseError> {
+    let s = s.trim();
+    if s == "-" {
+        return Err(ParseError::MissingValue);
+    }
+
+    if s.bytes().any(|c| c == b't' || c == b'z') {
+        return Err(ParseError::Malformed);
+    }
+
+    if let Some(i) = s.find('.') {
+        let digits = s[i + 1..].bytes().take_while(u8::is_ascii_digit).count();
+        if digits > 6 {
+            return Err(ParseError::TooLong);
+        }
+    }
+
+    Rfc3339Parser::new()
+        .mode(Mode::Strict)
+        .leap_second(LeapSecond::Reject)
+        .parse(s)
+}
+
+/// Parses a timestamp of the default Java and log4j layouts, (e.g. "2018-09-21 16:56:44,234"),
+/// or with a "T" separator, in the given zone as by [`parse_syslog`].
+/// The milliseconds `,SSS` are exactly 3 digits.
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{parse_log4j, parse_rfc3339, TimeZone};
+///
+/// let tz = TimeZone::new(8).unwrap();
+/// assert_eq!(
+///     parse_log4j("2018-09-21 16:56:44,234", tz),
+///     parse_rfc3339("2018-09-21T16:56:44.234+08:00")
+/// );
+/// ```
+///
+/// [`parse_syslog`]: ./fn.parse_syslog.html
+pub fn parse_log4j<Z: Zone>(s: &str, zone: Z) -> Result<Time, ParseError> {
+    let s = s.trim();
+    let description = match s.as_bytes().get(10) {
+        Some(b'T') => LOG4J_FORMATS[1],
+        _ => LOG4J_FORMATS[0],
+    };
+
+    Format::new(description)?.parse_in(s, &zone, Disambiguation::Compatible)
+}
+
+// fills in the year closest to the reference, among the years around it
+fn infer_year<Z: Zone>(mut fields: Fields, reference: &Time, zone: &Z) -> Result<Time, ParseError> {
+    let year = reference.to_date(zone).year();
+    let mut closest: Option<Time> = None;
+    let mut err = ParseError::Overflow;
+
+    for y in year - 1..=year + 1 {
+        fields.year = Some(y);
+        let t = match fields.to_time(zone, Disambiguation::Compatible) {
+            Ok(t) => t,
+            Err(e) => {
+                err = e;
+                continue;
+            }
+        };
+
+        let distance = |t: &Time| match t.duration_since(reference) {
+            Ok(d) | Err(d) => d,
+        };
+        if closest.as_ref().is_none_or(|c| distance(&t) < distance(c)) {
+            closest = Some(t);
+        }
+    }
+
+    closest.ok_or(err)
+}
@@ -0,0 +1,178 @@
+use super::{Time, TimeZone, Zone};
+use duration::{round, English, Locale, Unit};
+use std::time::{Duration, SystemTime};
+
+/// Describes relative times in a natural language
+pub trait RelativeLocale: Locale {
+    /// Returns the phrase of a time close to the reference, (e.g. "just now")
+    fn now(&self) -> String;
+
+    /// Returns the phrase of a time before the reference, (e.g. "3 minutes ago")
+    fn past(&self, phrase: &str) -> String;
+
+    /// Returns the phrase of a time after the reference, (e.g. "in 2 days")
+    fn future(&self, phrase: &str) -> String;
+}
+
+impl RelativeLocale for English {
+    fn now(&self) -> String {
+        "just now".to_owned()
+    }
+
+    fn past(&self, phrase: &str) -> String {
+        format!("{} ago", phrase)
+    }
+
+    fn future(&self, phrase: &str) -> String {
+        format!("in {}", phrase)
+    }
+}
+
+/// Formats a time relative to another one, (e.g. "3 minutes ago", "in 2 days")
+///
+/// By default, times within 10 seconds are "just now",
+/// and the phrase switches to the next unit at 45 seconds, 45 minutes and 22 hours.
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{RelativeFormatter, Time};
+/// use std::time::Duration;
+///
+/// let now = "2018-09-21T16:56:44Z".parse::<Time>().unwrap();
+/// let t1 = "2018-09-21T16:51:40Z".parse::<Time>().unwrap();
+/// let t2 = "2018-09-23T14:00:00Z".parse::<Time>().unwrap();
+///
+/// let f = RelativeFormatter::new();
+/// assert_eq!(f.format(&t1, &now), "5 minutes ago");
+/// assert_eq!(f.format(&t2, &now), "in 2 days");
+///
+/// let f = RelativeFormatter::new().absolute_after(Some(Duration::from_secs(86400)));
+/// assert_eq!(f.format(&t2, &now), "2018-09-23");
+/// ```
+#[derive(Debug, Clone)]
+pub struct RelativeFormatter<L: RelativeLocale = English, Z: Zone = TimeZone> {
+    locale: L,
+    now: Duration,
+    thresholds: [u64; 7],
+    absolute: Option<Duration>,
+    zone: Z,
+}
+
+impl RelativeFormatter {
+    /// Returns an English relative formatter with the default thresholds
+    pub fn new() -> RelativeFormatter {
+        RelativeFormatter::with_locale(English)
+    }
+}
+
+impl Default for RelativeFormatter {
+    fn default() -> RelativeFormatter {
+        RelativeFormatter::new()
+    }
+}
+
+impl<L: RelativeLocale> RelativeFormatter<L> {
+    /// Returns a relative formatter using the given locale, with the default thresholds
+    pub fn with_locale(locale: L) -> RelativeFormatter<L> {
+        let mut thresholds = [u64::MAX; 7];
+        thresholds[Unit::Second as usize] = 45;
+        thresholds[Unit::Minute as usize] = 45;
+        thresholds[Unit::Hour as usize] = 22;
+
+        RelativeFormatter {
+            locale,
+            now: Duration::from_secs(10),
+            thresholds,
+            absolute: None,
+            zone: TimeZone::utc(),
+        }
+    }
+}
+
+impl<L: RelativeLocale, Z: Zone> RelativeFormatter<L, Z> {
+    /// Sets the distance within which the time is described as now
+    pub fn now_within(mut self, d: Duration) -> RelativeFormatter<L, Z> {
+        self.now = d;
+        self
+    }
+
+    /// Sets the count of `unit` at which the phrase switches to the next larger unit,
+    /// (e.g. `threshold(Unit::Hour, 22)` turns 22 hours into "1 day").
+    /// Phrases use seconds at least and days at most.
+    pub fn threshold(mut self, unit: Unit, count: u64) -> RelativeFormatter<L, Z> {
+        self.thresholds[unit as usize] = count;
+        self
+    }
+
+    /// Sets the distance from which the date of the time is shown instead,
+    /// `None` means always relative
+    pub fn absolute_after(mut self, d: Option<Duration>) -> RelativeFormatter<L, Z> {
+        self.absolute = d;
+        self
+    }
+
+    /// Sets the zone in which absolute dates are shown, UTC by default
+    pub fn timezone<Y: Zone>(self, zone: Y) -> RelativeFormatter<L, Y> {
+        RelativeFormatter {
+            locale: self.locale,
+            now: self.now,
+            thresholds: self.thresholds,
+            absolute: self.absolute,
+            zone,
+        }
+    }
+
+    /// Returns the phrase of `t` relative to `reference`
+    pub fn format(&self, t: &Time, reference: &Time) -> String {
+        let (d, future) = match t.duration_since(reference) {
+            Ok(d) => (d, true),
+            Err(d) => (d, false),
+        };
+
+        if d < self.now {
+            return self.locale.now();
+        }
+
+        if let Some(absolute) = self.absolute {
+            if d >= absolute {
+                return t.to_date(&self.zone).to_string();
+            }
+        }
+
+        let units = [Unit::Second, Unit::Minute, Unit::Hour, Unit::Day];
+        let mut phrase = String::new();
+        for unit in units.iter() {
+            let count = count(d, *unit);
+            if *unit != Unit::Day && count >= self.thresholds[*unit as usize] {
+                continue;
+            }
+
+            phrase = self.locale.units(count, *unit);
+            break;
+        }
+
+        if future {
+            return self.locale.future(&phrase);
+        }
+
+        self.locale.past(&phrase)
+    }
+
+    /// Returns the phrase of `t` relative to the current system time
+    pub fn format_now(&self, t: &Time) -> String {
+        match Time::from_system_time(SystemTime::now()) {
+            Some(now) => self.format(t, &now),
+            // the system clock is out of the supported range
+            None => self.locale.now(),
+        }
+    }
+}
+
+// returns the count of units in d, rounded to the nearest
+fn count(d: Duration, unit: Unit) -> u64 {
+    let secs = unit.duration().as_secs();
+    match round(d, unit) {
+        Some(rounded) => rounded.as_secs() / secs,
+        None => d.as_secs() / secs,
+    }
+}
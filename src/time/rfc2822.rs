@@ -0,0 +1,279 @@
+use super::calendar::{month_from_abbr, Weekday, MONTH_ABBRS};
+use super::{civil_from_days, days_from_civil, LeapSecond, Time, TimeZone, Zone};
+use ParseError;
+
+/// [`RFC2822`] datetime parser with options, (e.g. "Fri, 21 Sep 2018 16:56:44 +0800")
+///
+/// Besides the [`RFC5322`] syntax, the parser accepts its obsolete forms:
+/// comments and folding whitespace anywhere between tokens, 2 or 3 digit years,
+/// and zone names "UT", "GMT", "EST", "EDT", "CST", "CDT", "MST", "MDT", "PST", "PDT".
+/// As suggested by RFC5322, military zones ("A" to "Z" except "J") are taken as UTC.
+/// A leap second is clamped to the end of the previous second.
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{parse_rfc3339, Rfc2822Parser};
+///
+/// let p = Rfc2822Parser::new();
+/// assert_eq!(
+///     p.parse("Fri, 21 Sep 2018 16:56:44 +0800"),
+///     parse_rfc3339("2018-09-21T16:56:44+08:00")
+/// );
+/// assert_eq!(
+///     p.parse("21 Sep 18 08:56 (sent from a phone) GMT"),
+///     parse_rfc3339("2018-09-21T08:56:00Z")
+/// );
+///
+/// let p = Rfc2822Parser::new().check_weekday(true);
+/// assert!(p.parse("Sat, 21 Sep 2018 16:56:44 +0800").is_err());
+/// ```
+///
+/// [`RFC2822`]: https://tools.ietf.org/html/rfc2822#section-3.3
+/// [`RFC5322`]: https://tools.ietf.org/html/rfc5322#section-3.3
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Rfc2822Parser {
+    check_weekday: bool,
+}
+
+impl Rfc2822Parser {
+    /// Returns a parser which ignores the day of week
+    pub fn new() -> Rfc2822Parser {
+        Rfc2822Parser::default()
+    }
+
+    /// Sets whether the day of week, if present, must match the date
+    pub fn check_weekday(mut self, check: bool) -> Rfc2822Parser {
+        self.check_weekday = check;
+        self
+    }
+
+    /// Parses a datetime string
+    pub fn parse(&self, s: &str) -> Result<Time, ParseError> {
+        let tokens = tokenize(s)?;
+        if tokens.is_empty() {
+            return Err(ParseError::EmptyInput);
+        }
+
+        let mut tokens = &tokens[..];
+        let mut weekday = None;
+        if tokens.len() > 1 && tokens[1] == "," {
+            weekday = Some(tokens[0].parse::<Weekday>()?);
+            tokens = &tokens[2..];
+        }
+
+        if tokens.len() < 5 {
+            return Err(ParseError::TooShort);
+        }
+
+        if tokens.len() > 5 {
+            return Err(ParseError::TooLong);
+        }
+
+        let day = read_number(&tokens[0], 1, 2)?;
+        let month = month_from_abbr(&tokens[1]).ok_or(ParseError::InvalidValue)?;
+        let year = read_year(&tokens[2])?;
+
+        let clock: Vec<&str> = tokens[3].split(':').collect();
+        if clock.len() < 2 || clock.len() > 3 {
+            return Err(ParseError::Malformed);
+        }
+
+        let hour = read_number(clock[0], 2, 2)?;
+        let minute = read_number(clock[1], 2, 2)?;
+        let second = match clock.get(2) {
+            Some(s) => read_number(s, 2, 2)?,
+            None => 0,
+        };
+
+        let tz = parse_zone(&tokens[4])?;
+
+        if let Some(weekday) = weekday {
+            if self.check_weekday
+                && weekday != Weekday::from_days(days_from_civil(year as i64, month, day))
+            {
+                return Err(ParseError::InvalidValue);
+            }
+        }
+
+        if second == 60 {
+            let t = Time::from_timetuple(year, month, day, hour, minute, 59, 0, tz)
+                .ok_or(ParseError::Overflow)?;
+            return LeapSecond::Clamp.apply(t);
+        }
+
+        Time::from_timetuple(year, month, day, hour, minute, second, 0, tz)
+            .ok_or(ParseError::Overflow)
+    }
+}
+
+/// Parses a [`RFC2822`] datetime string, (e.g. "Fri, 21 Sep 2018 16:56:44 +0800"),
+/// see [`Rfc2822Parser`] for more options.
+///
+/// [`RFC2822`]: https://tools.ietf.org/html/rfc2822#section-3.3
+/// [`Rfc2822Parser`]: ./struct.Rfc2822Parser.html
+pub fn parse_rfc2822(s: &str) -> Result<Time, ParseError> {
+    Rfc2822Parser::new().parse(s)
+}
+
+/// Formats the time as a [`RFC2822`] datetime string in the given zone,
+/// (e.g. "Fri, 21 Sep 2018 16:56:44 +0800"), the fraction of second is dropped.
+///
+/// The offset of the zone at the time is written as the alternate form of the `Display` of `TimeZone`,
+/// and the local time is in the offset of whole minutes like [`format_rfc3339`].
+/// Returns None if the local year is out of 0000 to 9999, which has no 4-digit form.
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{format_rfc2822, Time, TimeZone};
+///
+/// let t = "2018-09-21T08:56:44.5Z".parse::<Time>().unwrap();
+/// assert_eq!(
+///     format_rfc2822(&t, TimeZone::new(8).unwrap()).unwrap(),
+///     "Fri, 21 Sep 2018 16:56:44 +0800"
+/// );
+/// assert_eq!(
+///     format_rfc2822(&t, TimeZone::utc()).unwrap(),
+///     "Fri, 21 Sep 2018 08:56:44 +0000"
+/// );
+/// assert_eq!(format_rfc2822(&Time::MIN, TimeZone::utc()), None);
+/// ```
+///
+/// [`RFC2822`]: https://tools.ietf.org/html/rfc2822#section-3.3
+/// [`format_rfc3339`]: ./fn.format_rfc3339.html
+pub fn format_rfc2822<Z: Zone>(t: &Time, zone: Z) -> Option<String> {
+    let timezone = zone.offset_at(t).whole_minutes();
+    let (days, secs) = t.local_days(timezone);
+    let (year, month, day) = civil_from_days(days);
+    if !(0..=9999).contains(&year) {
+        return None;
+    }
+
+    Some(format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} {:#}",
+        Weekday::from_days(days).abbreviation(),
+        day,
+        MONTH_ABBRS[month as usize - 1],
+        year,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        timezone
+    ))
+}
+
+// splits the input into tokens, separating commas and dropping comments and folding whitespace,
+// (e.g. "Fri,21 Sep 2018 16 : 56 (comment) GMT" to ["Fri", ",", "21", "Sep", "2018", "16:56", "GMT"])
+fn tokenize(s: &str) -> Result<Vec<String>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut cur = String::new();
+    let mut depth = 0;
+    let mut escaped = false;
+    // the next token continues the current one, after a colon
+    let mut glue = false;
+
+    for c in s.chars() {
+        if depth > 0 {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '(' => {
+                depth = 1;
+                if !glue {
+                    flush(&mut tokens, &mut cur);
+                }
+            }
+            ')' => return Err(ParseError::Malformed),
+            ',' => {
+                flush(&mut tokens, &mut cur);
+                tokens.push(",".to_owned());
+                glue = false;
+            }
+            ':' => {
+                if cur.is_empty() && tokens.last().is_some_and(|t| t != ",") {
+                    cur = tokens.pop().unwrap_or_default();
+                }
+                cur.push(':');
+                glue = true;
+            }
+            _ if c.is_whitespace() => {
+                if !glue {
+                    flush(&mut tokens, &mut cur);
+                }
+            }
+            _ => {
+                cur.push(c);
+                glue = false;
+            }
+        }
+    }
+
+    if depth > 0 {
+        return Err(ParseError::Malformed);
+    }
+
+    flush(&mut tokens, &mut cur);
+    Ok(tokens)
+}
+
+fn flush(tokens: &mut Vec<String>, cur: &mut String) {
+    if !cur.is_empty() {
+        tokens.push(cur.split_off(0));
+    }
+}
+
+fn read_number(s: &str, min: usize, max: usize) -> Result<u32, ParseError> {
+    if s.len() < min || s.len() > max || !s.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(ParseError::InvalidValue);
+    }
+
+    s.parse::<u32>().or(Err(ParseError::InvalidValue))
+}
+
+// reads a year, 2-digit years are 1950 to 2049 and 3-digit years are after 1900
+fn read_year(s: &str) -> Result<u32, ParseError> {
+    let year = read_number(s, 2, 9)?;
+    match s.len() {
+        2 if year < 50 => Ok(year + 2000),
+        2 | 3 => Ok(year + 1900),
+        _ => Ok(year),
+    }
+}
+
+fn parse_zone(s: &str) -> Result<TimeZone, ParseError> {
+    let hours = match s.to_ascii_uppercase().as_str() {
+        "UT" | "GMT" => 0,
+        "EDT" => -4,
+        "EST" | "CDT" => -5,
+        "CST" | "MDT" => -6,
+        "MST" | "PDT" => -7,
+        "PST" => -8,
+        m if m.len() == 1 && m != "J" && m.bytes().all(|c| c.is_ascii_uppercase()) => 0,
+        _ => {
+            // non-ASCII input is rejected before slicing it by byte indices
+            let bs = s.as_bytes();
+            if bs.len() != 5 || !s.is_ascii() || (bs[0] != b'+' && bs[0] != b'-') {
+                return Err(ParseError::InvalidTimezone);
+            }
+
+            let hh = read_number(&s[1..3], 2, 2).or(Err(ParseError::InvalidTimezone))?;
+            let mm = read_number(&s[3..5], 2, 2).or(Err(ParseError::InvalidTimezone))?;
+            if hh > 23 || mm > 59 {
+                return Err(ParseError::InvalidTimezone);
+            }
+
+            let offset = (hh * 3600 + mm * 60) as i32;
+            let offset = if bs[0] == b'-' { -offset } else { offset };
+            return TimeZone::from_offset(offset).ok_or(ParseError::InvalidTimezone);
+        }
+    };
+
+    TimeZone::from_offset(hours * 3600).ok_or(ParseError::InvalidTimezone)
+}
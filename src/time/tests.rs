@@ -1,6 +1,27 @@
-use super::TimeZone;
-use super::{is_leap_year, parse_rfc3339, Time, UNIX_EPOCH};
+use super::Format;
+#[cfg(feature = "tzdb")]
+use super::Tz;
+use super::{
+    civil_from_days, days_from_civil, is_leap_year, parse_relative, parse_rfc3339, LeapSecond,
+    Mode, RelativeFormatter, Rfc3339Parser, Time, MAX_SECONDS, MIN_SECONDS, UNIX_EPOCH,
+};
+use super::{format_http_date, parse_http_date, parse_retry_after, RetryAfter};
+use super::{format_ordinal_date, format_week_date, parse_ordinal_date, parse_week_date};
+use super::{format_rfc2822, parse_rfc2822, parse_timestamp, Rfc2822Parser, Weekday};
+use super::{format_rfc3339, Interval};
+use super::{parse_clf, parse_log4j, parse_rfc5424, parse_syslog};
+use super::{Ambiguity, TimeZone};
+use super::{Date, DayOverflow, TimeOfDay};
+use super::{Disambiguation, LocalResult, LocalZone, PosixTz, Zone};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, Utc};
+use duration::Unit;
+use std::collections::HashMap;
+#[cfg(any(feature = "chrono", feature = "time"))]
+use std::convert::TryFrom;
 use std::time::{Duration, SystemTime};
+#[cfg(feature = "time")]
+use time03::{OffsetDateTime, UtcOffset};
 use ParseError;
 
 #[test]
@@ -132,7 +153,7 @@ fn test_1970_to_9999() {
             Time::from_timetuple(year, 1, 1, 0, 0, 0, 0, TimeZone::utc())
                 .unwrap()
                 .to_system_time()
-                == Some(SystemTime::UNIX_EPOCH + Duration::new(sec.clone(), 0)),
+                == Some(SystemTime::UNIX_EPOCH + Duration::new(sec, 0)),
             "{}-01-01",
             year,
         );
@@ -163,12 +184,14 @@ fn test_1970_to_9999() {
     }
 }
 
+type TimeTuple = (u32, u32, u32, u32, u32, u32, u32, TimeZone);
+
 #[test]
 fn test_invalid_timetuple() {
-    let tuples: Vec<(u32, u32, u32, u32, u32, u32, u32, TimeZone)> = vec![
-        (10000, 1, 1, 0, 0, 0, 0, TimeZone::utc()),
-        (0, 1, 1, 0, 0, 0, 0, TimeZone::new(1).unwrap()),
-        (9999, 12, 31, 23, 0, 0, 0, TimeZone::new(-1).unwrap()),
+    let tuples: Vec<TimeTuple> = vec![
+        (100000, 1, 1, 0, 0, 0, 0, TimeZone::utc()),
+        (u32::MAX, 1, 1, 0, 0, 0, 0, TimeZone::utc()),
+        (99999, 12, 31, 23, 0, 0, 0, TimeZone::new(-1).unwrap()),
         (1988, 0, 1, 0, 0, 0, 0, TimeZone::utc()),
         (1988, 13, 1, 0, 0, 0, 0, TimeZone::utc()),
         (1988, 1, 0, 0, 0, 0, 0, TimeZone::utc()),
@@ -364,3 +387,3018 @@ fn test_from_str() {
         ).unwrap())
     );
 }
+
+#[test]
+fn test_truncate_and_round() {
+    struct Case<'a> {
+        unit: Unit,
+        timezone: TimeZone,
+        truncate: &'a str,
+        round: &'a str,
+    }
+
+    let t = parse_rfc3339("2018-09-21T16:56:44.734867232+08:00").unwrap();
+    let cases: Vec<Case> = vec![
+        Case {
+            unit: Unit::Nanosecond,
+            timezone: TimeZone::utc(),
+            truncate: "2018-09-21T16:56:44.734867232+08:00",
+            round: "2018-09-21T16:56:44.734867232+08:00",
+        },
+        Case {
+            unit: Unit::Microsecond,
+            timezone: TimeZone::utc(),
+            truncate: "2018-09-21T16:56:44.734867+08:00",
+            round: "2018-09-21T16:56:44.734867+08:00",
+        },
+        Case {
+            unit: Unit::Millisecond,
+            timezone: TimeZone::utc(),
+            truncate: "2018-09-21T16:56:44.734+08:00",
+            round: "2018-09-21T16:56:44.735+08:00",
+        },
+        Case {
+            unit: Unit::Second,
+            timezone: TimeZone::utc(),
+            truncate: "2018-09-21T16:56:44+08:00",
+            round: "2018-09-21T16:56:45+08:00",
+        },
+        Case {
+            unit: Unit::Minute,
+            timezone: TimeZone::utc(),
+            truncate: "2018-09-21T16:56:00+08:00",
+            round: "2018-09-21T16:57:00+08:00",
+        },
+        Case {
+            unit: Unit::Hour,
+            timezone: TimeZone::utc(),
+            truncate: "2018-09-21T16:00:00+08:00",
+            round: "2018-09-21T17:00:00+08:00",
+        },
+        Case {
+            unit: Unit::Day,
+            timezone: TimeZone::utc(),
+            truncate: "2018-09-21T00:00:00Z",
+            round: "2018-09-21T00:00:00Z",
+        },
+        Case {
+            unit: Unit::Day,
+            timezone: TimeZone::new(8).unwrap(),
+            truncate: "2018-09-21T00:00:00+08:00",
+            round: "2018-09-22T00:00:00+08:00",
+        },
+        Case {
+            unit: Unit::Day,
+            timezone: TimeZone::new(-12).unwrap(),
+            truncate: "2018-09-20T00:00:00-12:00",
+            round: "2018-09-21T00:00:00-12:00",
+        },
+    ];
+
+    for c in cases {
+        assert_eq!(
+            t.truncate(c.unit, c.timezone),
+            Some(parse_rfc3339(c.truncate).unwrap()),
+            "{:?} {:?}",
+            c.unit,
+            c.timezone,
+        );
+        assert_eq!(
+            t.round(c.unit, c.timezone),
+            Some(parse_rfc3339(c.round).unwrap()),
+            "{:?} {:?}",
+            c.unit,
+            c.timezone,
+        );
+    }
+
+    let halfway = parse_rfc3339("2018-09-21T12:00:00Z").unwrap();
+    assert_eq!(
+        halfway.round(Unit::Day, TimeZone::utc()),
+        parse_rfc3339("2018-09-22T00:00:00Z").ok()
+    );
+
+    let min = parse_rfc3339("-99999-01-01T00:00:00Z").unwrap();
+    assert_eq!(min.truncate(Unit::Day, TimeZone::new(-1).unwrap()), None);
+    assert_eq!(min.round(Unit::Day, TimeZone::utc()), Some(min));
+
+    let max = parse_rfc3339("+99999-12-31T23:59:59.5Z").unwrap();
+    assert_eq!(
+        max.truncate(Unit::Second, TimeZone::utc()),
+        parse_rfc3339("+99999-12-31T23:59:59Z").ok()
+    );
+    assert_eq!(max.round(Unit::Second, TimeZone::utc()), None);
+}
+
+#[test]
+fn test_timetuple_leap_day() {
+    let feb29 = Time::from_timetuple(0, 2, 29, 0, 0, 0, 0, TimeZone::utc()).unwrap();
+    let mar1 = Time::from_timetuple(0, 3, 1, 0, 0, 0, 0, TimeZone::utc()).unwrap();
+    assert_ne!(feb29, mar1);
+    assert_eq!(feb29.sec, 59 * 86400);
+    assert_eq!(mar1.sec, 60 * 86400);
+
+    assert_eq!(
+        Time::from_timetuple(1970, 1, 1, 0, 0, 0, 0, TimeZone::utc()),
+        Some(UNIX_EPOCH)
+    );
+    assert_eq!(
+        Time::from_timetuple(2000, 3, 1, 0, 0, 0, 0, TimeZone::utc())
+            .unwrap()
+            .since(&Time::from_timetuple(2000, 2, 28, 0, 0, 0, 0, TimeZone::utc()).unwrap()),
+        Some(Duration::from_secs(2 * 86400))
+    );
+}
+
+#[test]
+fn test_civil_from_days() {
+    assert_eq!(civil_from_days(0), (0, 1, 1));
+    assert_eq!(civil_from_days(59), (0, 2, 29));
+    assert_eq!(civil_from_days(60), (0, 3, 1));
+    assert_eq!(civil_from_days(365), (0, 12, 31));
+    assert_eq!(civil_from_days(366), (1, 1, 1));
+    assert_eq!(civil_from_days(-1), (-1, 12, 31));
+    assert_eq!(civil_from_days(719528), (1970, 1, 1));
+    assert_eq!(UNIX_EPOCH.sec, 719528 * 86400);
+    assert_eq!(
+        Time::from_timetuple(0, 2, 29, 0, 0, 0, 0, TimeZone::utc()),
+        Some(Time {
+            sec: 59 * 86400,
+            nano: 0
+        })
+    );
+    assert_eq!(
+        Time::from_timetuple(0, 3, 1, 0, 0, 0, 0, TimeZone::utc()),
+        Some(Time {
+            sec: 60 * 86400,
+            nano: 0
+        })
+    );
+
+    for y in 1970..10000 {
+        let t = Time::from_timetuple(y, 2, 28, 0, 0, 0, 0, TimeZone::utc()).unwrap();
+        assert_eq!(civil_from_days(t.sec / 86400), (y as i64, 2, 28), "{}", y);
+        assert_eq!(
+            civil_from_days(t.sec / 86400 + 1),
+            if is_leap_year(y) {
+                (y as i64, 2, 29)
+            } else {
+                (y as i64, 3, 1)
+            },
+            "{}",
+            y
+        );
+    }
+}
+
+#[test]
+fn test_duration_since() {
+    let t1 = parse_rfc3339("2018-09-21T16:56:44.5Z").unwrap();
+    let t2 = parse_rfc3339("2018-09-21T16:57:44.25Z").unwrap();
+
+    assert_eq!(t2.duration_since(&t1), Ok(Duration::new(59, 750_000_000)));
+    assert_eq!(t1.duration_since(&t2), Err(Duration::new(59, 750_000_000)));
+    assert_eq!(t1.duration_since(&t1), Ok(Duration::new(0, 0)));
+    assert_eq!(t1.since(&t2), None);
+
+    assert_eq!(t1.checked_add(Duration::new(59, 750_000_000)), Some(t2));
+    assert_eq!(
+        UNIX_EPOCH.checked_sub(Duration::new(0, 1)),
+        parse_rfc3339("1969-12-31T23:59:59.999999999Z").ok()
+    );
+    assert_eq!(
+        parse_rfc3339("-99999-01-01T00:00:00.1Z")
+            .unwrap()
+            .checked_sub(Duration::new(0, 100_000_001)),
+        None
+    );
+    assert_eq!(
+        parse_rfc3339("+99999-12-31T23:59:59.9Z")
+            .unwrap()
+            .checked_add(Duration::new(0, 100_000_000)),
+        None
+    );
+
+    assert_eq!(
+        Time::from_system_time(SystemTime::UNIX_EPOCH + Duration::new(1537520204, 1)),
+        parse_rfc3339("2018-09-21T08:56:44.000000001Z").ok()
+    );
+    assert_eq!(
+        Time::from_system_time(SystemTime::UNIX_EPOCH - Duration::from_secs(86400)),
+        parse_rfc3339("1969-12-31T00:00:00Z").ok()
+    );
+}
+
+#[test]
+fn test_relative_format() {
+    let now = parse_rfc3339("2018-09-21T16:56:44Z").unwrap();
+    let cases: Vec<(u64, &str, &str)> = vec![
+        (0, "just now", "just now"),
+        (9, "just now", "just now"),
+        (10, "10 seconds ago", "in 10 seconds"),
+        (44, "44 seconds ago", "in 44 seconds"),
+        (45, "1 minute ago", "in 1 minute"),
+        (89, "1 minute ago", "in 1 minute"),
+        (90, "2 minutes ago", "in 2 minutes"),
+        (44 * 60 + 29, "44 minutes ago", "in 44 minutes"),
+        (44 * 60 + 30, "1 hour ago", "in 1 hour"),
+        (3 * 3600 + 1799, "3 hours ago", "in 3 hours"),
+        (21 * 3600 + 1799, "21 hours ago", "in 21 hours"),
+        (21 * 3600 + 1800, "1 day ago", "in 1 day"),
+        (36 * 3600, "2 days ago", "in 2 days"),
+        (400 * 86400, "400 days ago", "in 400 days"),
+    ];
+
+    let f = RelativeFormatter::new();
+    for c in cases {
+        let d = Duration::from_secs(c.0);
+        assert_eq!(f.format(&now.checked_sub(d).unwrap(), &now), c.1);
+        assert_eq!(f.format(&now.checked_add(d).unwrap(), &now), c.2);
+    }
+}
+
+#[test]
+fn test_relative_format_options() {
+    let now = parse_rfc3339("2018-09-21T16:56:44Z").unwrap();
+    let t = now.checked_sub(Duration::from_secs(30 * 3600)).unwrap();
+
+    let f = RelativeFormatter::new()
+        .now_within(Duration::from_secs(0))
+        .threshold(Unit::Second, 60)
+        .threshold(Unit::Hour, 48);
+    assert_eq!(f.format(&now, &now), "in 0 seconds");
+    assert_eq!(
+        f.format(&now.checked_add(Duration::from_secs(59)).unwrap(), &now),
+        "in 59 seconds"
+    );
+    assert_eq!(f.format(&t, &now), "30 hours ago");
+
+    let f = RelativeFormatter::new().absolute_after(Some(Duration::from_secs(86400)));
+    assert_eq!(f.format(&t, &now), "2018-09-20");
+    assert_eq!(
+        f.format(&t, &now),
+        f.clone()
+            .timezone(TimeZone::new(-10).unwrap())
+            .format(&t, &now)
+    );
+    assert_eq!(
+        f.timezone(TimeZone::new(-12).unwrap()).format(&t, &now),
+        "2018-09-19"
+    );
+}
+
+#[test]
+fn test_parse_relative() {
+    let now = parse_rfc3339("2018-09-21T16:56:44.5Z").unwrap();
+    let utc = TimeZone::utc();
+
+    let cases = vec![
+        ("now", "2018-09-21T16:56:44.5Z"),
+        (" Now ", "2018-09-21T16:56:44.5Z"),
+        ("today", "2018-09-21T00:00:00Z"),
+        ("yesterday", "2018-09-20T00:00:00Z"),
+        ("TOMORROW", "2018-09-22T00:00:00Z"),
+        ("3 days ago", "2018-09-18T16:56:44.5Z"),
+        ("1 day ago", "2018-09-20T16:56:44.5Z"),
+        ("an hour ago", "2018-09-21T15:56:44.5Z"),
+        ("in 2 hours", "2018-09-21T18:56:44.5Z"),
+        ("in a minute", "2018-09-21T16:57:44.5Z"),
+        ("in 1 hour 30 minutes", "2018-09-21T18:26:44.5Z"),
+        ("2h 30m ago", "2018-09-21T14:26:44.5Z"),
+        ("500 ms ago", "2018-09-21T16:56:44Z"),
+        ("in 0 seconds", "2018-09-21T16:56:44.5Z"),
+        ("now-15m", "2018-09-21T16:41:44.5Z"),
+        ("now + 1h", "2018-09-21T17:56:44.5Z"),
+        ("now-1d12h", "2018-09-20T04:56:44.5Z"),
+    ];
+
+    for (input, expect) in cases {
+        assert_eq!(
+            parse_relative(input, &now, utc),
+            parse_rfc3339(expect),
+            "input: {}",
+            input
+        );
+    }
+
+    let tz = TimeZone::new(8).unwrap();
+    assert_eq!(
+        parse_relative("today", &now, tz),
+        parse_rfc3339("2018-09-22T00:00:00+08:00")
+    );
+    assert_eq!(
+        parse_relative("yesterday", &now, tz),
+        parse_rfc3339("2018-09-21T00:00:00+08:00")
+    );
+}
+
+#[test]
+fn test_parse_relative_errors() {
+    let now = parse_rfc3339("2018-09-21T16:56:44Z").unwrap();
+    let utc = TimeZone::utc();
+
+    let cases = vec![
+        ("", ParseError::EmptyInput),
+        ("  ", ParseError::EmptyInput),
+        ("someday", ParseError::Malformed),
+        ("nowhere", ParseError::Malformed),
+        ("3 days", ParseError::Malformed),
+        ("in", ParseError::Malformed),
+        ("in ", ParseError::Malformed),
+        (" ago", ParseError::Malformed),
+        ("in days", ParseError::MissingValue),
+        ("in 3", ParseError::MissingUnit),
+        ("3 fortnights ago", ParseError::InvalidUnit),
+        ("now-", ParseError::EmptyInput),
+        ("now-15x", ParseError::InvalidUnit),
+        ("99999999999999999999 days ago", ParseError::Overflow),
+        ("40000000 days ago", ParseError::Overflow),
+        ("in 40000000 days", ParseError::Overflow),
+    ];
+
+    for (input, expect) in cases {
+        assert_eq!(
+            parse_relative(input, &now, utc),
+            Err(expect),
+            "input: {}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_parse_leap_second() {
+    let cases = vec![
+        (
+            "2016-12-31T23:59:60Z",
+            "2016-12-31T23:59:59.999999999Z",
+            "2017-01-01T00:00:00Z",
+        ),
+        (
+            "2016-12-31T23:59:60.5Z",
+            "2016-12-31T23:59:59.999999999Z",
+            "2017-01-01T00:00:00.5Z",
+        ),
+        (
+            "2017-01-01T07:59:60+08:00",
+            "2016-12-31T23:59:59.999999999Z",
+            "2017-01-01T00:00:00Z",
+        ),
+        (
+            "2015-06-30T18:59:60-05:00",
+            "2015-06-30T23:59:59.999999999Z",
+            "2015-07-01T00:00:00Z",
+        ),
+    ];
+
+    let clamp = Rfc3339Parser::new().leap_second(LeapSecond::Clamp);
+    let roll = Rfc3339Parser::new().leap_second(LeapSecond::Roll);
+    let reject = Rfc3339Parser::new().leap_second(LeapSecond::Reject);
+    for (s, clamped, rolled) in cases {
+        assert_eq!(parse_rfc3339(s), parse_rfc3339(clamped), "{}", s);
+        assert_eq!(clamp.parse(s), parse_rfc3339(clamped), "{}", s);
+        assert_eq!(roll.parse(s), parse_rfc3339(rolled), "{}", s);
+        assert_eq!(reject.parse(s), Err(ParseError::Overflow), "{}", s);
+    }
+
+    // not the last second of a UTC day
+    for s in &[
+        "2016-12-31T23:59:60+08:00",
+        "2016-12-31T23:58:60Z",
+        "2016-12-31T12:59:60Z",
+        "2016-12-31T23:59:61Z",
+    ] {
+        assert_eq!(clamp.parse(s), Err(ParseError::Overflow), "{}", s);
+        assert_eq!(roll.parse(s), Err(ParseError::Overflow), "{}", s);
+    }
+
+    assert_eq!(
+        roll.parse("+99999-12-31T23:59:60Z"),
+        Err(ParseError::Overflow)
+    );
+    assert_eq!(
+        clamp.parse("+99999-12-31T23:59:60Z"),
+        parse_rfc3339("+99999-12-31T23:59:59.999999999Z")
+    );
+
+    // from_timetuple keeps rejecting the second 60
+    assert_eq!(
+        Time::from_timetuple(2016, 12, 31, 23, 59, 60, 0, TimeZone::utc()),
+        None
+    );
+}
+
+#[test]
+fn test_parse_modes() {
+    let expect = Time::from_timetuple(2018, 9, 21, 8, 56, 44, 0, TimeZone::utc()).unwrap();
+    let with_nano = Time::from_timetuple(2018, 9, 21, 8, 56, 44, 234000000, TimeZone::utc());
+    let strict = Rfc3339Parser::new().mode(Mode::Strict);
+    let lenient = Rfc3339Parser::new().mode(Mode::Lenient);
+
+    // (input, accepted by strict)
+    let cases = vec![
+        ("2018-09-21T08:56:44Z", true),
+        ("2018-09-21t08:56:44z", true),
+        ("2018-09-21T16:56:44+08:00", true),
+        ("2018-09-21T03:26:44-05:30", true),
+        ("2018-09-22T07:56:44+23:00", true),
+        ("2018-09-21T08:56:44-00:00", true),
+        ("2018-09-21T16:56:44+0800", false),
+        ("2018-09-21T16:56:44+08", false),
+        ("2018-09-21 08:56:44Z", false),
+        ("2018-09-21T08:56:44", false),
+        ("20180921T085644Z", false),
+        ("20180921t165644+0800", false),
+        ("20180921 085644", false),
+    ];
+
+    for (s, valid) in cases {
+        assert_eq!(lenient.parse(s).as_ref(), Ok(&expect), "{}", s);
+        assert_eq!(strict.parse(s).is_ok(), valid, "{}", s);
+        if valid {
+            assert_eq!(strict.parse(s).as_ref(), Ok(&expect), "{}", s);
+        }
+    }
+
+    let cases = vec![
+        ("2018-09-21T08:56:44.234Z", true),
+        ("2018-09-21T08:56:44,234Z", false),
+        ("20180921T085644.234Z", false),
+        ("20180921T085644,234", false),
+        ("2018-09-21T08:56:44.2340000009Z", true),
+    ];
+
+    for (s, valid) in cases {
+        assert_eq!(lenient.parse(s).ok(), with_nano, "{}", s);
+        assert_eq!(strict.parse(s).is_ok(), valid, "{}", s);
+    }
+
+    // reduced precision
+    let cases = vec![
+        ("2018-09-21", "2018-09-21T00:00:00Z"),
+        ("20180921", "2018-09-21T00:00:00Z"),
+        ("2018-09-21T16:56Z", "2018-09-21T16:56:00Z"),
+        ("2018-09-21T16:56", "2018-09-21T16:56:00Z"),
+        ("20180921T1656+08", "2018-09-21T16:56:00+08:00"),
+    ];
+
+    for (s, full) in cases {
+        assert_eq!(lenient.parse(s), strict.parse(full), "{}", s);
+        assert!(strict.parse(s).is_err(), "{}", s);
+    }
+
+    let cases = vec![
+        ("2018-09-21", ParseError::TooShort),
+        ("2018-09-21T08:56:44", ParseError::TooShort),
+        ("2018-09-21T08:56:44.1", ParseError::InvalidTimezone),
+        ("2018-09-21 08:56:44Z", ParseError::Malformed),
+        ("2018-09-21T08:56:44,1Z", ParseError::Malformed),
+        ("2018-09-21T08:56Z", ParseError::TooShort),
+        ("2018-09-21T08:56:44+0800", ParseError::InvalidTimezone),
+        ("2018-09-21T08:56:44+24:00", ParseError::InvalidTimezone),
+        ("2018-09-21T08:56:44+08:60", ParseError::InvalidTimezone),
+        ("20180921T085644Z", ParseError::TooShort),
+    ];
+
+    for (s, err) in cases {
+        assert_eq!(strict.parse(s), Err(err), "{}", s);
+    }
+
+    let cases = vec![
+        ("2018-09-21T08:56:44+8", ParseError::InvalidTimezone),
+        ("2018-09-21T08:56:44+080", ParseError::InvalidTimezone),
+        ("2018-09-21T08:56:44+2400", ParseError::InvalidTimezone),
+        ("2018-09-21T08:5", ParseError::TooShort),
+        ("2018092", ParseError::TooShort),
+        ("20180921T08", ParseError::TooShort),
+        ("20180921X085644", ParseError::Malformed),
+    ];
+
+    for (s, err) in cases {
+        assert_eq!(lenient.parse(s), Err(err), "{}", s);
+    }
+}
+
+#[test]
+fn test_parse_timezone() {
+    let cases = vec![
+        ("", Some(0)),
+        ("Z", Some(0)),
+        ("z", Some(0)),
+        ("+00:00", Some(0)),
+        ("-00:00", Some(0)),
+        ("+08:00", Some(8 * 3600)),
+        ("+05:45", Some(5 * 3600 + 45 * 60)),
+        ("-09:30", Some(-(9 * 3600 + 30 * 60))),
+        ("+23:59", Some(86340)),
+        ("+24:00", None),
+        ("+08:60", None),
+        ("+0800", None),
+        ("08:00", None),
+        ("+8:00", None),
+        ("UTC", None),
+    ];
+
+    for (s, offset) in cases {
+        assert_eq!(
+            s.parse::<TimeZone>().ok().map(|tz| tz.offset()),
+            offset,
+            "{}",
+            s
+        );
+    }
+
+    assert_eq!(
+        TimeZone::from_offset(-86399).map(|tz| tz.offset()),
+        Some(-86399)
+    );
+    assert_eq!(TimeZone::from_offset(86400), None);
+    assert_eq!(TimeZone::from_offset(-86400), None);
+}
+
+#[test]
+fn test_timezone_display() {
+    // (offset, display, alternate, with sign)
+    let cases = vec![
+        (0, "Z", "+0000", "+00:00"),
+        (8 * 3600, "+08:00", "+0800", "+08:00"),
+        (-(9 * 3600 + 30 * 60), "-09:30", "-0930", "-09:30"),
+        (5 * 3600 + 45 * 60, "+05:45", "+0545", "+05:45"),
+        // the seconds of an offset are dropped
+        (5 * 3600 + 30 * 60 + 15, "+05:30", "+0530", "+05:30"),
+        (-86399, "-23:59", "-2359", "-23:59"),
+        (-15, "Z", "+0000", "+00:00"),
+    ];
+
+    for (offset, s, alternate, plus) in cases {
+        let tz = TimeZone::from_offset(offset).unwrap();
+        assert_eq!(tz.to_string(), s, "{}", s);
+        assert_eq!(format!("{:#}", tz), alternate, "{}", s);
+        assert_eq!(format!("{:+}", tz), plus, "{}", s);
+
+        let parsed = s.parse::<TimeZone>().unwrap();
+        assert_eq!(parsed.offset(), offset / 60 * 60, "{}", s);
+        assert_eq!(parsed.to_string(), s, "{}", s);
+        assert_eq!(plus.parse::<TimeZone>(), Ok(parsed), "{}", s);
+    }
+
+    // every format writes the same offset and the same time
+    let t = parse_rfc3339("2018-09-21T16:56:44Z").unwrap();
+    let tz = TimeZone::from_offset(5 * 3600 + 30 * 60 + 15).unwrap();
+    let s = format_rfc3339(&t, tz);
+    assert_eq!(s, "2018-09-21T22:26:44+05:30");
+    assert_eq!(parse_rfc3339(&s), Ok(t));
+    let s = format_rfc2822(&t, tz).unwrap();
+    assert_eq!(s, "Fri, 21 Sep 2018 22:26:44 +0530");
+    assert_eq!(parse_rfc2822(&s), Ok(t));
+    for f in ["%F %T %z", "%F %T %:z"].iter() {
+        let f = Format::new(f).unwrap();
+        assert_eq!(f.parse(&f.format(&t, tz)), Ok(t));
+    }
+}
+
+#[test]
+fn test_timezone_abbreviation() {
+    let cases = vec![
+        ("UTC", Ambiguity::Reject, Ok(0)),
+        ("gmt", Ambiguity::Reject, Ok(0)),
+        (" Z ", Ambiguity::Reject, Ok(0)),
+        ("EST", Ambiguity::Reject, Ok(-5 * 3600)),
+        ("EDT", Ambiguity::Europe, Ok(-4 * 3600)),
+        ("JST", Ambiguity::Reject, Ok(9 * 3600)),
+        ("CET", Ambiguity::Asia, Ok(3600)),
+        ("NPT", Ambiguity::Reject, Ok(5 * 3600 + 45 * 60)),
+        ("NST", Ambiguity::Reject, Ok(-(3 * 3600 + 30 * 60))),
+        ("CST", Ambiguity::Reject, Err(ParseError::InvalidTimezone)),
+        ("CST", Ambiguity::America, Ok(-6 * 3600)),
+        ("cst", Ambiguity::Asia, Ok(8 * 3600)),
+        ("CST", Ambiguity::Europe, Err(ParseError::InvalidTimezone)),
+        ("IST", Ambiguity::Asia, Ok(5 * 3600 + 30 * 60)),
+        ("IST", Ambiguity::Europe, Ok(3600)),
+        ("IST", Ambiguity::America, Err(ParseError::InvalidTimezone)),
+        ("BST", Ambiguity::Europe, Ok(3600)),
+        ("BST", Ambiguity::Asia, Ok(6 * 3600)),
+        ("AST", Ambiguity::America, Ok(-4 * 3600)),
+        ("AST", Ambiguity::Asia, Ok(3 * 3600)),
+        ("XYZ", Ambiguity::America, Err(ParseError::InvalidTimezone)),
+        (
+            "+08:00",
+            Ambiguity::Reject,
+            Err(ParseError::InvalidTimezone),
+        ),
+        ("", Ambiguity::Reject, Err(ParseError::EmptyInput)),
+    ];
+
+    for (s, ambiguity, offset) in cases {
+        assert_eq!(
+            TimeZone::from_abbreviation(s, ambiguity).map(|tz| tz.offset()),
+            offset,
+            "{}",
+            s
+        );
+    }
+
+    assert_eq!(Ambiguity::default(), Ambiguity::Reject);
+}
+
+#[test]
+fn test_weekday() {
+    let cases = vec![
+        ("0000-01-01T00:00:00Z", Weekday::Saturday),
+        ("1970-01-01T00:00:00Z", Weekday::Thursday),
+        ("2000-02-29T00:00:00Z", Weekday::Tuesday),
+        ("2018-09-21T16:56:44Z", Weekday::Friday),
+        ("2018-09-23T23:59:59Z", Weekday::Sunday),
+        ("2018-09-24T00:00:00Z", Weekday::Monday),
+        ("9999-12-31T00:00:00Z", Weekday::Friday),
+    ];
+
+    for (s, weekday) in cases {
+        let t = parse_rfc3339(s).unwrap();
+        assert_eq!(
+            Weekday::from_days(t.local_days(TimeZone::utc()).0),
+            weekday,
+            "{}",
+            s
+        );
+    }
+
+    assert_eq!("mon".parse::<Weekday>(), Ok(Weekday::Monday));
+    assert_eq!("Wednesday".parse::<Weekday>(), Ok(Weekday::Wednesday));
+    assert_eq!("SUN".parse::<Weekday>(), Ok(Weekday::Sunday));
+    assert_eq!("Mo".parse::<Weekday>(), Err(ParseError::InvalidValue));
+    assert_eq!(Weekday::Thursday.abbreviation(), "Thu");
+}
+
+#[test]
+fn test_parse_rfc2822() {
+    let cases = vec![
+        (
+            "Fri, 21 Sep 2018 16:56:44 +0800",
+            "2018-09-21T16:56:44+08:00",
+        ),
+        ("21 Sep 2018 16:56:44 +0800", "2018-09-21T16:56:44+08:00"),
+        ("Fri,21 Sep 2018 16:56 -0230", "2018-09-21T16:56:00-02:30"),
+        ("fri, 1 sep 2018 16:56:44 gmt", "2018-09-01T16:56:44Z"),
+        ("Fri, 21 Sep 2018 16:56:44 UT", "2018-09-21T16:56:44Z"),
+        ("Fri, 21 Sep 2018 16:56:44 Z", "2018-09-21T16:56:44Z"),
+        ("Fri, 21 Sep 2018 16:56:44 A", "2018-09-21T16:56:44Z"),
+        ("Fri, 21 Sep 2018 16:56:44 EST", "2018-09-21T16:56:44-05:00"),
+        ("Fri, 21 Sep 2018 16:56:44 EDT", "2018-09-21T16:56:44-04:00"),
+        ("Fri, 21 Sep 2018 16:56:44 CST", "2018-09-21T16:56:44-06:00"),
+        ("Fri, 21 Sep 2018 16:56:44 CDT", "2018-09-21T16:56:44-05:00"),
+        ("Fri, 21 Sep 2018 16:56:44 MST", "2018-09-21T16:56:44-07:00"),
+        ("Fri, 21 Sep 2018 16:56:44 MDT", "2018-09-21T16:56:44-06:00"),
+        ("Fri, 21 Sep 2018 16:56:44 PST", "2018-09-21T16:56:44-08:00"),
+        ("Fri, 21 Sep 2018 16:56:44 PDT", "2018-09-21T16:56:44-07:00"),
+        ("Fri, 21 Sep 18 16:56:44 +0000", "2018-09-21T16:56:44Z"),
+        ("Sun, 21 Sep 68 16:56:44 +0000", "1968-09-21T16:56:44Z"),
+        ("Fri, 21 Sep 118 16:56:44 +0000", "2018-09-21T16:56:44Z"),
+        (
+            "  Fri (Friday) ,\r\n 21 Sep\r\n 2018 16 : 56 : 44 +0800 (CST (China))",
+            "2018-09-21T16:56:44+08:00",
+        ),
+        (
+            "Fri, 21 Sep 2018 16:56:44 (a \\) comment) +0800",
+            "2018-09-21T16:56:44+08:00",
+        ),
+        (
+            "Sat, 31 Dec 2016 23:59:60 +0000",
+            "2016-12-31T23:59:59.999999999Z",
+        ),
+        // the weekday is not checked by default
+        (
+            "Mon, 21 Sep 2018 16:56:44 +0800",
+            "2018-09-21T16:56:44+08:00",
+        ),
+    ];
+
+    for (s, expect) in cases {
+        assert_eq!(parse_rfc2822(s), parse_rfc3339(expect), "{}", s);
+    }
+
+    let p = Rfc2822Parser::new().check_weekday(true);
+    assert_eq!(
+        p.parse("Fri, 21 Sep 2018 16:56:44 +0800"),
+        parse_rfc3339("2018-09-21T16:56:44+08:00")
+    );
+    assert_eq!(
+        p.parse("Friday, 21 Sep 2018 16:56:44 +0800"),
+        parse_rfc3339("2018-09-21T16:56:44+08:00")
+    );
+    assert_eq!(
+        p.parse("Mon, 21 Sep 2018 16:56:44 +0800"),
+        Err(ParseError::InvalidValue)
+    );
+}
+
+#[test]
+fn test_parse_rfc2822_errors() {
+    let cases = vec![
+        ("", ParseError::EmptyInput),
+        (" (comment) ", ParseError::EmptyInput),
+        ("Fri, 21 Sep 2018 16:56:44", ParseError::TooShort),
+        ("Fri, 21 Sep 2018 16:56:44 +0800 GMT", ParseError::TooLong),
+        ("Fry, 21 Sep 2018 16:56:44 +0800", ParseError::InvalidValue),
+        ("Fri, 21 Sept 2018 16:56:44 +0800", ParseError::InvalidValue),
+        ("Fri, 021 Sep 2018 16:56:44 +0800", ParseError::InvalidValue),
+        ("Fri, 21 Sep 8 16:56:44 +0800", ParseError::InvalidValue),
+        ("Fri, 21 Sep 2018 6:56:44 +0800", ParseError::InvalidValue),
+        ("Fri, 21 Sep 2018 16:56:44:00 +0800", ParseError::Malformed),
+        ("Fri, 21 Sep 2018 16-56-44 +0800", ParseError::Malformed),
+        (
+            "Fri, 21 Sep 2018 16:56:44 (comment +0800",
+            ParseError::Malformed,
+        ),
+        ("Fri, 21 Sep 2018 16:56:44 +0800)", ParseError::Malformed),
+        (
+            "Fri, 21 Sep 2018 16:56:44 +08:00",
+            ParseError::InvalidTimezone,
+        ),
+        (
+            "Fri, 21 Sep 2018 16:56:44 +2400",
+            ParseError::InvalidTimezone,
+        ),
+        ("Fri, 21 Sep 2018 16:56:44 J", ParseError::InvalidTimezone),
+        ("21 Sep 2018 16:56:44 +0é0", ParseError::InvalidTimezone),
+        ("21 Sep 2018 16:56:44 ééT", ParseError::InvalidTimezone),
+        ("Fri, 21 Sep 2018 16:56:44 CET", ParseError::InvalidTimezone),
+        ("Fri, 31 Sep 2018 16:56:44 +0800", ParseError::Overflow),
+        ("Fri, 21 Sep 2018 24:56:44 +0800", ParseError::Overflow),
+        ("Fri, 21 Sep 2018 16:56:60 +0800", ParseError::Overflow),
+    ];
+
+    for (s, err) in cases {
+        assert_eq!(parse_rfc2822(s), Err(err), "{}", s);
+    }
+}
+
+#[test]
+fn test_format_rfc2822() {
+    let cases = vec![
+        (
+            "2018-09-21T16:56:44.5+08:00",
+            8 * 3600,
+            "Fri, 21 Sep 2018 16:56:44 +0800",
+        ),
+        (
+            "2018-09-21T16:56:44+08:00",
+            -3600,
+            "Fri, 21 Sep 2018 07:56:44 -0100",
+        ),
+        (
+            "2018-09-01T00:00:00Z",
+            -(2 * 3600 + 30 * 60),
+            "Fri, 31 Aug 2018 21:30:00 -0230",
+        ),
+        ("0000-01-01T00:00:00Z", 0, "Sat, 01 Jan 0000 00:00:00 +0000"),
+        ("9999-12-31T23:59:59Z", 0, "Fri, 31 Dec 9999 23:59:59 +0000"),
+    ];
+
+    for (s, offset, expect) in cases {
+        let t = parse_rfc3339(s).unwrap();
+        let tz = TimeZone::from_offset(offset).unwrap();
+        assert_eq!(format_rfc2822(&t, tz).unwrap(), expect, "{}", s);
+        assert_eq!(
+            parse_rfc2822(expect),
+            parse_rfc3339(s).map(|t| t.truncate(Unit::Second, tz).unwrap()),
+            "{}",
+            s
+        );
+    }
+
+    // years without 4 digits
+    let cases = vec![
+        (Time::MIN, 0),
+        (Time::MAX, 0),
+        (parse_rfc3339("-0001-12-31T23:59:59Z").unwrap(), 0),
+        (parse_rfc3339("0000-01-01T00:00:00Z").unwrap(), -3600),
+        (parse_rfc3339("9999-12-31T23:00:00Z").unwrap(), 3600),
+        (parse_rfc3339("+10000-01-01T00:00:00Z").unwrap(), 0),
+    ];
+    for (t, offset) in cases {
+        let tz = TimeZone::from_offset(offset).unwrap();
+        assert_eq!(format_rfc2822(&t, tz), None, "{:?}", t);
+    }
+}
+
+#[test]
+fn test_parse_http_date() {
+    let cases = vec![
+        ("Sun, 06 Nov 1994 08:49:37 GMT", "1994-11-06T08:49:37Z"),
+        ("Sunday, 06-Nov-94 08:49:37 GMT", "1994-11-06T08:49:37Z"),
+        ("Sun Nov  6 08:49:37 1994", "1994-11-06T08:49:37Z"),
+        ("Sun Nov 16 08:49:37 1994", "1994-11-16T08:49:37Z"),
+        ("Thursday, 01-Jan-00 00:00:00 GMT", "2000-01-01T00:00:00Z"),
+        (
+            "Wed, 31 Dec 2016 23:59:60 GMT",
+            "2016-12-31T23:59:59.999999999Z",
+        ),
+        // the weekday is not checked
+        ("Mon, 06 Nov 1994 08:49:37 GMT", "1994-11-06T08:49:37Z"),
+        (" Sun, 06 Nov 1994 08:49:37 GMT ", "1994-11-06T08:49:37Z"),
+    ];
+
+    for (s, expect) in cases {
+        assert_eq!(parse_http_date(s), parse_rfc3339(expect), "{}", s);
+    }
+
+    let cases = vec![
+        ("", ParseError::EmptyInput),
+        ("Sun, 06 Nov 1994 08:49:37", ParseError::Malformed),
+        ("Sun 06 Nov 1994 08:49:37 GMT", ParseError::Malformed),
+        ("Sun, Nov  6 08:49:37 1994", ParseError::Malformed),
+        ("Sunday, 06/Nov/94 08:49:37 GMT", ParseError::Malformed),
+        ("Sun, 06 Nov 1994 08-49-37 GMT", ParseError::Malformed),
+        ("Sun, 06 Nov 1994 8:49:37 GMT", ParseError::Malformed),
+        ("Sun, 06 Nov 1994 08:49:37 UTC", ParseError::InvalidTimezone),
+        (
+            "Sun, 06 Nov 1994 08:49:37 +0000",
+            ParseError::InvalidTimezone,
+        ),
+        ("Sun, 6 Nov 1994 08:49:37 GMT", ParseError::InvalidValue),
+        ("Sun, 06 Nov 94 08:49:37 GMT", ParseError::InvalidValue),
+        (
+            "Sun, 06 November 1994 08:49:37 GMT",
+            ParseError::InvalidValue,
+        ),
+        ("Sundae, 06 Nov 1994 08:49:37 GMT", ParseError::InvalidValue),
+        ("Sun, 06 Nov 1994 08:4a:37 GMT", ParseError::InvalidValue),
+        ("Sun Nov 106 08:49:37 1994", ParseError::InvalidValue),
+        ("Sun, 31 Nov 1994 08:49:37 GMT", ParseError::Overflow),
+        ("Sun, 06 Nov 1994 08:49:60 GMT", ParseError::Overflow),
+    ];
+
+    for (s, err) in cases {
+        assert_eq!(parse_http_date(s), Err(err), "{}", s);
+    }
+}
+
+#[test]
+fn test_format_http_date() {
+    let cases = vec![
+        ("1994-11-06T08:49:37Z", "Sun, 06 Nov 1994 08:49:37 GMT"),
+        (
+            "1994-11-06T08:49:37.999-05:00",
+            "Sun, 06 Nov 1994 13:49:37 GMT",
+        ),
+        ("1970-01-01T00:00:00Z", "Thu, 01 Jan 1970 00:00:00 GMT"),
+    ];
+
+    for (s, expect) in cases {
+        let t = parse_rfc3339(s).unwrap();
+        assert_eq!(format_http_date(&t).unwrap(), expect, "{}", s);
+        assert_eq!(
+            parse_http_date(expect),
+            Ok(t.truncate(Unit::Second, TimeZone::utc()).unwrap())
+        );
+    }
+
+    // years without 4 digits
+    let cases = vec![
+        Time::MIN,
+        Time::MAX,
+        parse_rfc3339("-0001-12-31T23:59:59Z").unwrap(),
+        parse_rfc3339("+10000-01-01T00:00:00Z").unwrap(),
+    ];
+    for t in cases {
+        assert_eq!(format_http_date(&t), None, "{:?}", t);
+    }
+    assert_eq!(
+        format_http_date(&parse_rfc3339("0000-01-01T00:00:00Z").unwrap()).unwrap(),
+        "Sat, 01 Jan 0000 00:00:00 GMT"
+    );
+}
+
+#[test]
+fn test_parse_retry_after() {
+    let received = parse_rfc3339("1999-12-31T23:57:59Z").unwrap();
+    let cases = vec![
+        (
+            "120",
+            Ok(RetryAfter::Delay(Duration::from_secs(120))),
+            parse_rfc3339("1999-12-31T23:59:59Z").ok(),
+        ),
+        (
+            " 0 ",
+            Ok(RetryAfter::Delay(Duration::from_secs(0))),
+            parse_rfc3339("1999-12-31T23:57:59Z").ok(),
+        ),
+        (
+            "Fri, 31 Dec 1999 23:59:59 GMT",
+            Ok(RetryAfter::Date(
+                parse_rfc3339("1999-12-31T23:59:59Z").unwrap(),
+            )),
+            parse_rfc3339("1999-12-31T23:59:59Z").ok(),
+        ),
+        (
+            "18446744073709551615",
+            Ok(RetryAfter::Delay(Duration::from_secs(u64::MAX))),
+            None,
+        ),
+        ("18446744073709551616", Err(ParseError::Overflow), None),
+        ("-1", Err(ParseError::Malformed), None),
+        ("1.5", Err(ParseError::Malformed), None),
+        ("", Err(ParseError::EmptyInput), None),
+    ];
+
+    for (s, expect, time) in cases {
+        let r = parse_retry_after(s);
+        assert_eq!(r, expect, "{}", s);
+        if let Ok(r) = r {
+            assert_eq!(r.time(&received), time, "{}", s);
+        }
+    }
+}
+
+#[test]
+fn test_parse_timestamp() {
+    let cases = vec![
+        ("1537520204", None, "2018-09-21T08:56:44Z"),
+        ("1537520204.234", None, "2018-09-21T08:56:44.234Z"),
+        (
+            "1537520204.2340000019",
+            None,
+            "2018-09-21T08:56:44.234000001Z",
+        ),
+        (" @1537520204 ", None, "2018-09-21T08:56:44Z"),
+        ("+1537520204", None, "2018-09-21T08:56:44Z"),
+        ("1537520204234", None, "2018-09-21T08:56:44.234Z"),
+        ("1537520204234567", None, "2018-09-21T08:56:44.234567Z"),
+        (
+            "1537520204234567891",
+            None,
+            "2018-09-21T08:56:44.234567891Z",
+        ),
+        ("1537520204234.5", None, "2018-09-21T08:56:44.2345Z"),
+        ("0", None, "1970-01-01T00:00:00Z"),
+        (".5", None, "1970-01-01T00:00:00.5Z"),
+        ("1.", None, "1970-01-01T00:00:01Z"),
+        ("-1.5", None, "1969-12-31T23:59:58.5Z"),
+        ("@-62167219200", None, "0000-01-01T00:00:00Z"),
+        ("99999999999", None, "5138-11-16T09:46:39Z"),
+        ("100000000000", None, "1973-03-03T09:46:40Z"),
+        (
+            "1537520204",
+            Some(Unit::Millisecond),
+            "1970-01-18T19:05:20.204Z",
+        ),
+        (
+            "1537520204",
+            Some(Unit::Nanosecond),
+            "1970-01-01T00:00:01.537520204Z",
+        ),
+        ("17795.5", Some(Unit::Day), "2018-09-21T12:00:00Z"),
+        (
+            "1537520204.5",
+            Some(Unit::Microsecond),
+            "1970-01-01T00:25:37.5202045Z",
+        ),
+    ];
+
+    for (s, unit, expect) in cases {
+        assert_eq!(parse_timestamp(s, unit), parse_rfc3339(expect), "{}", s);
+    }
+
+    let cases = vec![
+        ("", ParseError::EmptyInput),
+        ("@", ParseError::MissingValue),
+        ("-.", ParseError::MissingValue),
+        ("1e9", ParseError::InvalidValue),
+        ("1.5.5", ParseError::InvalidValue),
+        ("--1", ParseError::InvalidValue),
+        ("0x10", ParseError::InvalidValue),
+        ("18446744073709551616", ParseError::Overflow),
+    ];
+
+    for (s, err) in cases {
+        assert_eq!(parse_timestamp(s, None), Err(err), "{}", s);
+    }
+
+    assert_eq!(
+        parse_timestamp("3093527980800", Some(Unit::Second)),
+        Err(ParseError::Overflow)
+    );
+    assert_eq!(
+        parse_timestamp("-3217830796801", Some(Unit::Second)),
+        Err(ParseError::Overflow)
+    );
+}
+
+#[test]
+fn test_timestamp() {
+    let cases = vec![
+        (
+            "2018-09-21T08:56:44.234567891Z",
+            1537520204,
+            1537520204234,
+            1537520204234567,
+            Some(1537520204234567891),
+        ),
+        ("1970-01-01T00:00:00Z", 0, 0, 0, Some(0)),
+        (
+            "1969-12-31T23:59:59.5Z",
+            -1,
+            -500,
+            -500000,
+            Some(-500000000),
+        ),
+        (
+            "0000-01-01T00:00:00Z",
+            -62167219200,
+            -62167219200000,
+            -62167219200000000,
+            None,
+        ),
+        (
+            "9999-12-31T23:59:59.999999999Z",
+            253402300799,
+            253402300799999,
+            253402300799999999,
+            None,
+        ),
+        (
+            "-99999-01-01T00:00:00Z",
+            -3217830796800,
+            -3217830796800000,
+            -3217830796800000000,
+            None,
+        ),
+        (
+            "+99999-12-31T23:59:59.999999999Z",
+            3093527980799,
+            3093527980799999,
+            3093527980799999999,
+            None,
+        ),
+    ];
+
+    for (s, secs, millis, micros, nanos) in cases {
+        let t = parse_rfc3339(s).unwrap();
+        assert_eq!(t.timestamp(), secs, "{}", s);
+        assert_eq!(t.timestamp_millis(), millis, "{}", s);
+        assert_eq!(t.timestamp_micros(), micros, "{}", s);
+        assert_eq!(t.timestamp_nanos(), nanos, "{}", s);
+        assert_eq!(Time::from_timestamp(secs, t.nano), Some(t), "{}", s);
+    }
+
+    assert_eq!(
+        Time::from_timestamp(-1, 500_000_000),
+        parse_rfc3339("1969-12-31T23:59:59.5Z").ok()
+    );
+    assert_eq!(Time::from_timestamp(0, 1_000_000_000), None);
+    assert_eq!(Time::from_timestamp(3093527980800, 0), None);
+    assert_eq!(Time::from_timestamp(-3217830796801, 0), None);
+    assert_eq!(Time::from_timestamp(i64::MIN, 0), None);
+}
+
+#[test]
+fn test_format_parse() {
+    let cases = vec![
+        (
+            "%d/%b/%Y:%H:%M:%S %z",
+            "21/Sep/2018:16:56:44 +0800",
+            "2018-09-21T16:56:44+08:00",
+        ),
+        ("%Y.%m.%d %Hh%M", "2018.09.21 16h56", "2018-09-21T16:56:00Z"),
+        ("%F %T", "2018-09-21 16:56:44", "2018-09-21T16:56:44Z"),
+        ("%F %T%%", "2018-09-21 16:56:44%", "2018-09-21T16:56:44Z"),
+        ("%Y%m%d%H%M%S", "20180921165644", "2018-09-21T16:56:44Z"),
+        ("%Y-%m-%d", "2018-9-1", "2018-09-01T00:00:00Z"),
+        ("%b %e %T", "Sep  1 16:56:44", "1970-09-01T16:56:44Z"),
+        ("%b %e %T", "sep 21 16:56:44", "1970-09-21T16:56:44Z"),
+        (
+            "%A, %d %B %Y %I:%M %p",
+            "Friday, 21 September 2018 04:56 PM",
+            "2018-09-21T16:56:00Z",
+        ),
+        ("%a %I%p", "Fri 12am", "1970-01-01T00:00:00Z"),
+        ("%a %I%p", "Fri 12PM", "1970-01-01T12:00:00Z"),
+        ("%Y-%j", "2018-264", "2018-09-21T00:00:00Z"),
+        ("%Y-%j", "2016-366", "2016-12-31T00:00:00Z"),
+        ("%y%m%d", "180921", "2018-09-21T00:00:00Z"),
+        ("%y%m%d", "690921", "1969-09-21T00:00:00Z"),
+        ("%T.%f", "16:56:44.5", "1970-01-01T16:56:44.5Z"),
+        ("%T,%3f", "16:56:44,234", "1970-01-01T16:56:44.234Z"),
+        ("%T,%6f", "16:56:44,000567", "1970-01-01T16:56:44.000567Z"),
+        ("%T%z", "16:56:44Z", "1970-01-01T16:56:44Z"),
+        ("%T%z", "16:56:44-05:30", "1970-01-01T16:56:44-05:30"),
+        ("%T %:z", "16:56:44 +08", "1970-01-01T16:56:44+08:00"),
+        (
+            "%F %T",
+            "2016-12-31 23:59:60",
+            "2016-12-31T23:59:59.999999999Z",
+        ),
+        ("Date: %F", "Date: 2018-09-21", "2018-09-21T00:00:00Z"),
+        ("%F", "-0044-03-15", "-0044-03-15T00:00:00Z"),
+        ("%F", "+12345-01-01", "+12345-01-01T00:00:00Z"),
+        ("%F", "+2018-09-21", "2018-09-21T00:00:00Z"),
+        ("%Y-%j", "-0004-366", "-0004-12-31T00:00:00Z"),
+    ];
+
+    for (desc, s, expect) in cases {
+        let f = Format::new(desc).unwrap();
+        assert_eq!(f.parse(s), parse_rfc3339(expect), "{} {}", desc, s);
+    }
+
+    let f = "%F %T".parse::<Format>().unwrap();
+    assert_eq!(
+        f.clone()
+            .timezone(TimeZone::new(8).unwrap())
+            .parse("2018-09-21 16:56:44"),
+        parse_rfc3339("2018-09-21T16:56:44+08:00")
+    );
+
+    let cases = vec![
+        ("%F %T", "2018-09-21", ParseError::TooShort),
+        ("%F %T", "2018-09-21 16:56:44 ", ParseError::TooLong),
+        ("%F %T", "2018/09/21 16:56:44", ParseError::Malformed),
+        ("%F %T", "2018-09-21 16:x6:44", ParseError::InvalidValue),
+        ("%b %d", "Spt 21", ParseError::InvalidValue),
+        ("%a %d", "Fry 21", ParseError::InvalidValue),
+        ("%T.%f", "16:56:44.", ParseError::TooShort),
+        ("%T.%f", "16:56:44.x", ParseError::InvalidValue),
+        ("%T,%3f", "16:56:44,23", ParseError::InvalidValue),
+        ("%T,%3f", "16:56:44,234567", ParseError::TooLong),
+        ("%T,%6f", "16:56:44,234", ParseError::InvalidValue),
+        ("%T,%9f", "16:56:44,23456789", ParseError::InvalidValue),
+        ("%T%z", "16:56:44+8", ParseError::InvalidTimezone),
+        ("%T%z", "16:56:44+25:00", ParseError::InvalidTimezone),
+        ("%F", "2018-02-29", ParseError::Overflow),
+        ("%Y-%j", "2018-366", ParseError::Overflow),
+        ("%Y-%j", "2018-000", ParseError::Overflow),
+        ("%I %p", "13 PM", ParseError::Overflow),
+        ("%F %T", "2018-09-21 24:00:00", ParseError::Overflow),
+        ("%F", "-44-03-15", ParseError::InvalidValue),
+        ("%F", "+100000-01-01", ParseError::Overflow),
+        ("%F", "", ParseError::EmptyInput),
+    ];
+
+    for (desc, s, err) in cases {
+        let f = Format::new(desc).unwrap();
+        assert_eq!(f.parse(s), Err(err), "{} {}", desc, s);
+    }
+
+    for desc in &["%", "%Q", "%Y-%", "%4f", "%:", "%:Y"] {
+        assert_eq!(Format::new(desc), Err(ParseError::Malformed), "{}", desc);
+    }
+}
+
+#[test]
+fn test_format_format() {
+    let t = parse_rfc3339("2018-09-01T04:06:08.234567891Z").unwrap();
+    let cases = vec![
+        ("%d/%b/%Y:%H:%M:%S %z", 8, "01/Sep/2018:12:06:08 +0800"),
+        ("%F %T %:z", -5, "2018-08-31 23:06:08 -05:00"),
+        ("%A, %e %B %y", 0, "Saturday,  1 September 18"),
+        ("%a %j %I:%M %p", 0, "Sat 244 04:06 AM"),
+        ("%a %j %I:%M %p", 12, "Sat 244 04:06 PM"),
+        ("%I %p", -4, "12 AM"),
+        ("%T.%f", 0, "04:06:08.234567891"),
+        ("%T.%3f|%6f|%9f", 0, "04:06:08.234|234567|234567891"),
+        ("100%% %Y", 0, "100% 2018"),
+    ];
+
+    for (desc, hours, expect) in cases {
+        let f = Format::new(desc).unwrap();
+        let tz = TimeZone::new(hours).unwrap();
+        assert_eq!(f.format(&t, tz), expect, "{}", desc);
+    }
+
+    let f = Format::new("%F %T %:z").unwrap();
+    let tz = TimeZone::from_offset(-(9 * 3600 + 30 * 60)).unwrap();
+    assert_eq!(f.format(&t, tz), "2018-08-31 18:36:08 -09:30");
+    assert_eq!(
+        f.parse(&f.format(&t, tz)),
+        Ok(t.truncate(Unit::Second, tz).unwrap())
+    );
+}
+
+#[test]
+fn test_parse_presets() {
+    let cases = vec![
+        ("10/Oct/2000:13:55:36 -0700", "2000-10-10T13:55:36-07:00"),
+        ("[10/Oct/2000:13:55:36 -0700]", "2000-10-10T13:55:36-07:00"),
+        ("21/Sep/2018:16:56:44 +0800", "2018-09-21T16:56:44+08:00"),
+    ];
+
+    for (s, expect) in cases {
+        assert_eq!(parse_clf(s), parse_rfc3339(expect), "{}", s);
+    }
+
+    assert_eq!(
+        parse_clf("[10/Oct/2000:13:55:36 -0700"),
+        Err(ParseError::Malformed)
+    );
+    assert_eq!(parse_clf("10/Oct/2000:13:55:36"), Err(ParseError::TooShort));
+
+    let cases = vec![
+        ("2003-10-11T22:14:15.003Z", Ok("2003-10-11T22:14:15.003Z")),
+        (
+            "1985-04-12T19:20:50.52-04:00",
+            Ok("1985-04-12T23:20:50.52Z"),
+        ),
+        (
+            "2003-08-24T05:14:15.000003-07:00",
+            Ok("2003-08-24T12:14:15.000003Z"),
+        ),
+        (
+            "2003-08-24T05:14:15.000000003-07:00",
+            Err(ParseError::TooLong),
+        ),
+        ("2003-10-11t22:14:15.003Z", Err(ParseError::Malformed)),
+        ("2003-10-11T22:14:15.003z", Err(ParseError::Malformed)),
+        ("2003-10-11 22:14:15.003Z", Err(ParseError::Malformed)),
+        ("2003-10-11T22:14:15", Err(ParseError::TooShort)),
+        ("2016-12-31T23:59:60Z", Err(ParseError::Overflow)),
+        ("-", Err(ParseError::MissingValue)),
+    ];
+
+    for (s, expect) in cases {
+        assert_eq!(parse_rfc5424(s), expect.and_then(parse_rfc3339), "{}", s);
+    }
+
+    let tz = TimeZone::new(8).unwrap();
+    let cases = vec![
+        ("2018-09-21 16:56:44,234", "2018-09-21T16:56:44.234+08:00"),
+        ("2018-09-21T16:56:44,234", "2018-09-21T16:56:44.234+08:00"),
+    ];
+
+    for (s, expect) in cases {
+        assert_eq!(parse_log4j(s, tz), parse_rfc3339(expect), "{}", s);
+    }
+
+    assert_eq!(
+        parse_log4j("2018-09-21 16:56:44.234", tz),
+        Err(ParseError::Malformed)
+    );
+    assert_eq!(
+        parse_log4j("2018-09-21 16:56:44,2", tz),
+        Err(ParseError::InvalidValue)
+    );
+    assert_eq!(
+        parse_log4j("2018-09-21 16:56:44,123456789", tz),
+        Err(ParseError::TooLong)
+    );
+    assert_eq!(
+        parse_log4j("2018-09-21T16:56:44,1234", tz),
+        Err(ParseError::TooLong)
+    );
+}
+
+#[test]
+fn test_parse_syslog() {
+    let utc = TimeZone::utc();
+    let cases = vec![
+        (
+            "2018-09-21T16:56:44Z",
+            "Oct 11 22:14:15",
+            "2018-10-11T22:14:15Z",
+        ),
+        (
+            "2018-09-21T16:56:44Z",
+            "Sep  1 00:00:00",
+            "2018-09-01T00:00:00Z",
+        ),
+        (
+            "2018-09-21T16:56:44Z",
+            "Mar 22 16:56:44",
+            "2019-03-22T16:56:44Z",
+        ),
+        (
+            "2018-09-21T16:56:44Z",
+            "Mar 23 16:56:44",
+            "2018-03-23T16:56:44Z",
+        ),
+        (
+            "2019-01-01T00:00:01Z",
+            "Dec 31 23:59:59",
+            "2018-12-31T23:59:59Z",
+        ),
+        (
+            "2018-12-31T23:59:59Z",
+            "Jan  1 00:00:01",
+            "2019-01-01T00:00:01Z",
+        ),
+        (
+            "2019-03-01T00:00:00Z",
+            "Feb 29 12:00:00",
+            "2020-02-29T12:00:00Z",
+        ),
+        (
+            "2016-03-01T00:00:00Z",
+            "Feb 29 12:00:00",
+            "2016-02-29T12:00:00Z",
+        ),
+        (
+            "0000-01-01T00:00:00Z",
+            "Dec 31 23:59:59",
+            "-0001-12-31T23:59:59Z",
+        ),
+    ];
+
+    for (reference, s, expect) in cases {
+        let reference = parse_rfc3339(reference).unwrap();
+        assert_eq!(
+            parse_syslog(s, &reference, utc),
+            parse_rfc3339(expect),
+            "{}",
+            s
+        );
+    }
+
+    let reference = parse_rfc3339("2018-12-31T20:00:00Z").unwrap();
+    assert_eq!(
+        parse_syslog("Jan  1 01:00:00", &reference, TimeZone::new(8).unwrap()),
+        parse_rfc3339("2019-01-01T01:00:00+08:00")
+    );
+    assert_eq!(
+        parse_syslog("Feb 30 00:00:00", &reference, utc),
+        Err(ParseError::Overflow)
+    );
+    assert_eq!(
+        parse_syslog("Feb 21", &reference, utc),
+        Err(ParseError::TooShort)
+    );
+}
+
+#[test]
+fn test_zone_fixed() {
+    let tz = TimeZone::new(8).unwrap();
+    let wall = "2018-09-21T16:56:44.5".parse::<Time>().unwrap();
+    let t = parse_rfc3339("2018-09-21T16:56:44.5+08:00").unwrap();
+    assert_eq!(tz.offset_at(&t), tz);
+    assert_eq!(tz.local(&wall), LocalResult::Single(t));
+    assert_eq!(
+        tz.resolve(&wall, Disambiguation::Reject),
+        parse_rfc3339("2018-09-21T16:56:44.5+08:00").ok()
+    );
+
+    let c = tz.components(&parse_rfc3339("2018-12-31T20:00:00.5Z").unwrap());
+    assert_eq!(
+        (c.year, c.month, c.day, c.hour, c.minute, c.second, c.nano),
+        (2019, 1, 1, 4, 0, 0, 500_000_000)
+    );
+    assert_eq!((c.weekday, c.timezone), (Weekday::Tuesday, tz));
+
+    let c = Time::from_timetuple(0, 1, 1, 0, 0, 0, 0, TimeZone::utc())
+        .unwrap()
+        .components(TimeZone::new(-5).unwrap());
+    assert_eq!((c.year, c.month, c.day, c.hour), (-1, 12, 31, 19));
+
+    let wall = "-99999-01-01T02:00:00".parse::<Time>().unwrap();
+    assert_eq!(tz.local(&wall), LocalResult::None);
+}
+
+// builds a TZif file of the transitions and the local time types (offset, dst, abbreviation)
+#[cfg(feature = "tzdb")]
+fn tzif(version: u8, transitions: &[(i64, u8)], types: &[(i32, u8, &str)]) -> Vec<u8> {
+    let mut chars = Vec::new();
+    let mut records = Vec::new();
+    for &(offset, dst, abbr) in types {
+        records.extend_from_slice(&offset.to_be_bytes());
+        records.push(dst);
+        records.push(chars.len() as u8);
+        chars.extend_from_slice(abbr.as_bytes());
+        chars.push(0);
+    }
+
+    let block = |time_size: usize| {
+        let mut data = b"TZif".to_vec();
+        data.push(version);
+        data.extend_from_slice(&[0; 15]);
+        for &n in [0, 0, 0, transitions.len(), types.len(), chars.len()].iter() {
+            data.extend_from_slice(&(n as u32).to_be_bytes());
+        }
+        for &(t, _) in transitions {
+            match time_size {
+                4 => data.extend_from_slice(&(t as i32).to_be_bytes()),
+                _ => data.extend_from_slice(&t.to_be_bytes()),
+            }
+        }
+        data.extend(transitions.iter().map(|&(_, i)| i));
+        data.extend_from_slice(&records);
+        data.extend_from_slice(&chars);
+        data
+    };
+
+    let mut data = block(4);
+    if version >= b'2' {
+        data.extend(block(8));
+        data.extend_from_slice(b"\nEST5EDT,M3.2.0,M11.1.0\n");
+    }
+    data
+}
+
+#[cfg(feature = "tzdb")]
+#[test]
+fn test_tzif() {
+    // America/New_York in 2018
+    let transitions = [(1520751600, 1), (1541311200, 0)];
+    let types = [(-18000, 0, "EST"), (-14400, 1, "EDT")];
+
+    for &version in [0, b'2', b'3'].iter() {
+        let tz = Tz::from_tzif("America/New_York", &tzif(version, &transitions, &types)).unwrap();
+        assert_eq!(tz.name(), "America/New_York");
+
+        let cases = vec![
+            ("2018-01-01T00:00:00Z", -5, "EST", false),
+            ("2018-03-11T06:59:59Z", -5, "EST", false),
+            ("2018-03-11T07:00:00Z", -4, "EDT", true),
+            ("2018-11-04T05:59:59Z", -4, "EDT", true),
+            ("2018-11-04T06:00:00Z", -5, "EST", false),
+            ("1900-01-01T00:00:00Z", -5, "EST", false),
+        ];
+
+        for (s, hours, abbr, dst) in cases {
+            let t = parse_rfc3339(s).unwrap();
+            assert_eq!(tz.offset_at(&t), TimeZone::new(hours).unwrap(), "{}", s);
+            assert_eq!(tz.abbreviation_at(&t), abbr, "{}", s);
+            assert_eq!(tz.is_dst_at(&t), dst, "{}", s);
+        }
+
+        let cases = vec![
+            (
+                "2018-03-11T01:59:59",
+                LocalResult::Single(parse_rfc3339("2018-03-11T01:59:59-05:00").unwrap()),
+            ),
+            (
+                "2018-03-11T02:30:00",
+                LocalResult::Skipped(
+                    parse_rfc3339("2018-03-11T01:30:00-05:00").unwrap(),
+                    parse_rfc3339("2018-03-11T03:30:00-04:00").unwrap(),
+                ),
+            ),
+            (
+                "2018-03-11T03:00:00",
+                LocalResult::Single(parse_rfc3339("2018-03-11T03:00:00-04:00").unwrap()),
+            ),
+            (
+                "2018-11-04T01:30:00",
+                LocalResult::Ambiguous(
+                    parse_rfc3339("2018-11-04T01:30:00-04:00").unwrap(),
+                    parse_rfc3339("2018-11-04T01:30:00-05:00").unwrap(),
+                ),
+            ),
+            (
+                "2018-11-04T02:00:00",
+                LocalResult::Single(parse_rfc3339("2018-11-04T02:00:00-05:00").unwrap()),
+            ),
+        ];
+
+        for (s, expect) in cases {
+            let wall = s.parse::<Time>().unwrap();
+            assert_eq!(tz.local(&wall), expect, "{}", s);
+        }
+
+        let gap = "2018-03-11T02:30:00".parse::<Time>().unwrap();
+        let overlap = "2018-11-04T01:30:00".parse::<Time>().unwrap();
+        let cases = vec![
+            (
+                Disambiguation::Compatible,
+                "2018-03-11T03:30:00-04:00",
+                "2018-11-04T01:30:00-04:00",
+            ),
+            (
+                Disambiguation::Earlier,
+                "2018-03-11T01:30:00-05:00",
+                "2018-11-04T01:30:00-04:00",
+            ),
+            (
+                Disambiguation::Later,
+                "2018-03-11T03:30:00-04:00",
+                "2018-11-04T01:30:00-05:00",
+            ),
+        ];
+
+        for (d, expect_gap, expect_overlap) in cases {
+            assert_eq!(
+                tz.resolve(&gap, d),
+                parse_rfc3339(expect_gap).ok(),
+                "{:?}",
+                d
+            );
+            assert_eq!(
+                tz.resolve(&overlap, d),
+                parse_rfc3339(expect_overlap).ok(),
+                "{:?}",
+                d
+            );
+        }
+        assert_eq!(tz.resolve(&gap, Disambiguation::Reject), None);
+        assert_eq!(tz.resolve(&overlap, Disambiguation::Reject), None);
+
+        // the footer rule after the last transition
+        let t = parse_rfc3339("2038-07-01T00:00:00Z").unwrap();
+        let expect = if version == 0 { "EST" } else { "EDT" };
+        assert_eq!(tz.abbreviation_at(&t), expect);
+        assert_eq!(tz.is_dst_at(&t), version != 0);
+
+        let t = parse_rfc3339("2018-11-04T05:30:00Z").unwrap();
+        let c = tz.components(&t);
+        assert_eq!((c.hour, c.minute), (1, 30));
+        assert_eq!(c.timezone, TimeZone::new(-4).unwrap());
+    }
+}
+
+#[cfg(feature = "tzdb")]
+#[test]
+fn test_tzif_errors() {
+    let types = [(0, 0, "UTC")];
+    let data = tzif(b'2', &[], &types);
+    assert!(Tz::from_tzif("UTC", &data).is_ok());
+
+    let mut cases = vec![
+        (b"".to_vec(), ParseError::TooShort),
+        (data[..data.len() / 2].to_vec(), ParseError::TooShort),
+        (
+            b"TZjf".iter().chain(&data[4..]).cloned().collect(),
+            ParseError::Malformed,
+        ),
+        (tzif(b'1', &[], &types), ParseError::Malformed),
+        (tzif(b'2', &[], &[]), ParseError::Malformed),
+        (tzif(b'2', &[(0, 1)], &types), ParseError::Malformed),
+        (tzif(b'2', &[(1, 0), (0, 0)], &types), ParseError::Malformed),
+        (tzif(b'2', &[], &[(86400, 0, "X")]), ParseError::Malformed),
+        (tzif(b'2', &[], &[(0, 2, "X")]), ParseError::Malformed),
+    ];
+
+    // a huge count in the header of the 64-bit data, which must not be allocated
+    let v2 = 44 + 6 + "UTC\0".len();
+    let mut huge = data[..v2 + 32].to_vec();
+    huge.extend_from_slice(&0xFFFF_FFF0_u32.to_be_bytes());
+    huge.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 0]);
+    cases.push((huge, ParseError::TooShort));
+
+    let footer_len = "EST5EDT,M3.2.0,M11.1.0\n".len();
+    let mut bad_footer = data[..data.len() - footer_len].to_vec();
+    bad_footer.extend_from_slice(b"EST\n");
+    let mut trailing = data.clone();
+    trailing.push(0);
+    let mut no_newline = data.clone();
+    no_newline.pop();
+    cases.push((bad_footer, ParseError::Malformed));
+    cases.push((trailing, ParseError::Malformed));
+    cases.push((no_newline, ParseError::Malformed));
+
+    for (data, expect) in cases {
+        assert_eq!(Tz::from_tzif("", &data), Err(expect), "{:?}", data);
+    }
+
+    // an empty footer
+    let mut data = data[..data.len() - footer_len].to_vec();
+    data.push(b'\n');
+    let tz = Tz::from_tzif("UTC", &data).unwrap();
+    assert_eq!(tz.abbreviation_at(&UNIX_EPOCH), "UTC");
+
+    for name in ["", "/etc/localtime", "../zoneinfo/UTC", "America/../UTC"].iter() {
+        let err = Tz::load(name).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{}", name);
+    }
+
+    let err = Tz::load("No/Such_Zone").unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+// checks the zoneinfo database of the system, skipped where it is not installed
+#[cfg(feature = "tzdb")]
+#[test]
+fn test_tz_load() {
+    let new_york = match Tz::load("America/New_York") {
+        Ok(tz) => tz,
+        Err(_) => return,
+    };
+
+    let wall = "2018-03-11T02:30:00".parse::<Time>().unwrap();
+    assert_eq!(
+        new_york.resolve(&wall, Disambiguation::Compatible),
+        parse_rfc3339("2018-03-11T03:30:00-04:00").ok()
+    );
+
+    let t = parse_rfc3339("2038-07-01T00:00:00Z").unwrap();
+    assert_eq!(new_york.abbreviation_at(&t), "EDT");
+    let t = parse_rfc3339("2100-12-01T00:00:00Z").unwrap();
+    assert_eq!(new_york.abbreviation_at(&t), "EST");
+
+    let shanghai = Tz::load("Asia/Shanghai").unwrap();
+    let cases = vec![
+        ("2018-09-21T08:56:44Z", 8 * 3600, "CST"),
+        ("1988-07-01T00:00:00Z", 9 * 3600, "CDT"),
+        ("1900-01-01T00:00:00Z", 8 * 3600 + 5 * 60 + 43, "LMT"),
+    ];
+
+    for (s, offset, abbr) in cases {
+        let t = parse_rfc3339(s).unwrap();
+        assert_eq!(shanghai.offset_at(&t).offset(), offset, "{}", s);
+        assert_eq!(shanghai.abbreviation_at(&t), abbr, "{}", s);
+    }
+}
+
+#[test]
+fn test_posix_tz() {
+    let cases = vec![
+        ("UTC0", "2018-07-01T00:00:00Z", 0, "UTC", false),
+        ("<+08>-8", "2018-07-01T00:00:00Z", 8 * 3600, "+08", false),
+        (
+            "IST-5:30",
+            "2018-07-01T00:00:00Z",
+            5 * 3600 + 1800,
+            "IST",
+            false,
+        ),
+        (
+            "NPT-5:45",
+            "2018-07-01T00:00:00Z",
+            5 * 3600 + 2700,
+            "NPT",
+            false,
+        ),
+        ("LMT+0:05:43", "2018-07-01T00:00:00Z", -343, "LMT", false),
+        // US rules, and the same by default
+        (
+            "CST6CDT,M3.2.0,M11.1.0",
+            "2018-03-11T07:59:59Z",
+            -6 * 3600,
+            "CST",
+            false,
+        ),
+        (
+            "CST6CDT,M3.2.0,M11.1.0",
+            "2018-03-11T08:00:00Z",
+            -5 * 3600,
+            "CDT",
+            true,
+        ),
+        (
+            "CST6CDT,M3.2.0,M11.1.0",
+            "2018-11-04T06:59:59Z",
+            -5 * 3600,
+            "CDT",
+            true,
+        ),
+        (
+            "CST6CDT,M3.2.0,M11.1.0",
+            "2018-11-04T07:00:00Z",
+            -6 * 3600,
+            "CST",
+            false,
+        ),
+        ("CST6CDT", "2018-03-11T08:00:00Z", -5 * 3600, "CDT", true),
+        ("CST6CDT5", "2018-03-11T08:00:00Z", -5 * 3600, "CDT", true),
+        // European rules with transitions at 01:00 UTC
+        (
+            "CET-1CEST,M3.5.0,M10.5.0/3",
+            "2018-03-25T00:59:59Z",
+            3600,
+            "CET",
+            false,
+        ),
+        (
+            "CET-1CEST,M3.5.0,M10.5.0/3",
+            "2018-03-25T01:00:00Z",
+            7200,
+            "CEST",
+            true,
+        ),
+        (
+            "CET-1CEST,M3.5.0,M10.5.0/3",
+            "2018-10-28T00:59:59Z",
+            7200,
+            "CEST",
+            true,
+        ),
+        (
+            "CET-1CEST,M3.5.0,M10.5.0/3",
+            "2018-10-28T01:00:00Z",
+            3600,
+            "CET",
+            false,
+        ),
+        // southern hemisphere, daylight saving time across the new year
+        (
+            "AEST-10AEDT,M10.1.0,M4.1.0/3",
+            "2018-01-01T00:00:00Z",
+            11 * 3600,
+            "AEDT",
+            true,
+        ),
+        (
+            "AEST-10AEDT,M10.1.0,M4.1.0/3",
+            "2018-03-31T15:59:59Z",
+            11 * 3600,
+            "AEDT",
+            true,
+        ),
+        (
+            "AEST-10AEDT,M10.1.0,M4.1.0/3",
+            "2018-03-31T16:00:00Z",
+            10 * 3600,
+            "AEST",
+            false,
+        ),
+        (
+            "AEST-10AEDT,M10.1.0,M4.1.0/3",
+            "2018-10-06T16:00:00Z",
+            11 * 3600,
+            "AEDT",
+            true,
+        ),
+        // Julian and zero-based days of year
+        (
+            "XST3XDT,J60/0,J300/0",
+            "2016-03-01T02:59:59Z",
+            -3 * 3600,
+            "XST",
+            false,
+        ),
+        (
+            "XST3XDT,J60/0,J300/0",
+            "2016-03-01T03:00:00Z",
+            -2 * 3600,
+            "XDT",
+            true,
+        ),
+        (
+            "XST3XDT,59/0,300/0",
+            "2016-02-29T03:00:00Z",
+            -2 * 3600,
+            "XDT",
+            true,
+        ),
+        (
+            "XST3XDT,59/0,300/0",
+            "2017-03-01T02:59:59Z",
+            -3 * 3600,
+            "XST",
+            false,
+        ),
+        // daylight saving time all year, as in RFC8536
+        (
+            "EST5EDT,0/0,J365/25",
+            "2018-01-01T04:00:00Z",
+            -4 * 3600,
+            "EDT",
+            true,
+        ),
+        (
+            "EST5EDT,0/0,J365/25",
+            "2018-12-31T23:00:00Z",
+            -4 * 3600,
+            "EDT",
+            true,
+        ),
+        // extended negative and large transition times
+        (
+            "<-03>3<-02>,M3.5.0/-2,M10.5.0/-1",
+            "2018-03-25T00:59:59Z",
+            -3 * 3600,
+            "-03",
+            false,
+        ),
+        (
+            "<-03>3<-02>,M3.5.0/-2,M10.5.0/-1",
+            "2018-03-25T01:00:00Z",
+            -2 * 3600,
+            "-02",
+            true,
+        ),
+        (
+            "IST-2IDT,M3.4.4/26,M10.5.0",
+            "2018-03-23T00:00:00Z",
+            3 * 3600,
+            "IDT",
+            true,
+        ),
+    ];
+
+    for (s, time, offset, abbr, dst) in cases {
+        let tz = s.parse::<PosixTz>().expect(s);
+        let t = parse_rfc3339(time).unwrap();
+        assert_eq!(tz.offset_at(&t).offset(), offset, "{} {}", s, time);
+        assert_eq!(tz.abbreviation_at(&t), abbr, "{} {}", s, time);
+        assert_eq!(tz.is_dst_at(&t), dst, "{} {}", s, time);
+    }
+
+    let tz = PosixTz::new("CST6CDT,M3.2.0,M11.1.0").unwrap();
+    let wall = "2018-03-11T02:30:00".parse::<Time>().unwrap();
+    assert_eq!(
+        tz.resolve(&wall, Disambiguation::Compatible),
+        parse_rfc3339("2018-03-11T03:30:00-05:00").ok()
+    );
+}
+
+#[test]
+fn test_posix_tz_errors() {
+    let cases = vec![
+        ("", ParseError::EmptyInput),
+        ("CST", ParseError::MissingValue),
+        ("C6", ParseError::Malformed),
+        ("<+08-8", ParseError::Malformed),
+        ("<+0 8>-8", ParseError::Malformed),
+        ("CST+", ParseError::MissingValue),
+        ("CST6:60", ParseError::InvalidValue),
+        ("CST6:5", ParseError::InvalidValue),
+        ("CST25", ParseError::InvalidValue),
+        ("CST24", ParseError::InvalidTimezone),
+        ("CST6CDT,", ParseError::MissingValue),
+        ("CST6CDT,M3.2.0", ParseError::Malformed),
+        ("CST6CDT,M3.2.0,", ParseError::MissingValue),
+        ("CST6CDT,M13.2.0,M11.1.0", ParseError::InvalidValue),
+        ("CST6CDT,M3.6.0,M11.1.0", ParseError::InvalidValue),
+        ("CST6CDT,M3.2.7,M11.1.0", ParseError::InvalidValue),
+        ("CST6CDT,M3.2,M11.1.0", ParseError::Malformed),
+        ("CST6CDT,J0,J365", ParseError::InvalidValue),
+        ("CST6CDT,0,366", ParseError::InvalidValue),
+        ("CST6CDT,M3.2.0/168,M11.1.0", ParseError::InvalidValue),
+        ("CST6CDT,M3.2.0,M11.1.0,", ParseError::Malformed),
+        ("CST6CDT,M3.2.0x,M11.1.0", ParseError::Malformed),
+        ("CST6 CDT", ParseError::Malformed),
+    ];
+
+    for (s, expect) in cases {
+        assert_eq!(PosixTz::new(s), Err(expect), "{}", s);
+    }
+}
+
+#[test]
+fn test_zone_arithmetic() {
+    let tz = PosixTz::new("EST5EDT,M3.2.0,M11.1.0").unwrap();
+    let t = |s: &str| parse_rfc3339(s).unwrap();
+
+    let cases = vec![
+        (
+            "2018-03-11T12:00:00-04:00",
+            Unit::Day,
+            "2018-03-11T00:00:00-05:00",
+        ),
+        (
+            "2018-11-04T12:00:00-05:00",
+            Unit::Day,
+            "2018-11-04T00:00:00-04:00",
+        ),
+        (
+            "2018-11-04T01:30:00-04:00",
+            Unit::Hour,
+            "2018-11-04T01:00:00-04:00",
+        ),
+        (
+            "2018-11-04T01:30:00-05:00",
+            Unit::Hour,
+            "2018-11-04T01:00:00-05:00",
+        ),
+        (
+            "2018-03-11T03:30:00-04:00",
+            Unit::Hour,
+            "2018-03-11T03:00:00-04:00",
+        ),
+    ];
+    for (s, unit, expect) in cases {
+        assert_eq!(t(s).truncate(unit, &tz), Some(t(expect)), "{}", s);
+    }
+    assert_eq!(
+        t("2018-03-11T12:00:00-04:00").round(Unit::Day, &tz),
+        Some(t("2018-03-12T00:00:00-04:00"))
+    );
+    assert_eq!(
+        t("2018-11-04T11:00:00-05:00").round(Unit::Day, &tz),
+        Some(t("2018-11-04T00:00:00-04:00"))
+    );
+
+    let cases = vec![
+        ("2018-03-10T12:00:00-05:00", 1, "2018-03-11T12:00:00-04:00"),
+        ("2018-03-10T02:30:00-05:00", 1, "2018-03-11T03:30:00-04:00"),
+        ("2018-11-03T01:30:00-04:00", 1, "2018-11-04T01:30:00-04:00"),
+        ("2018-11-05T01:30:00-05:00", -1, "2018-11-04T01:30:00-05:00"),
+        ("2018-11-04T12:00:00-05:00", -1, "2018-11-03T12:00:00-04:00"),
+    ];
+    for (s, days, expect) in cases {
+        assert_eq!(t(s).checked_add_days(days, &tz), Some(t(expect)), "{}", s);
+    }
+    assert_eq!(
+        t("2018-02-11T12:00:00-05:00").checked_add_months(1, DayOverflow::Clamp, &tz),
+        Some(t("2018-03-11T12:00:00-04:00"))
+    );
+    assert_eq!(
+        t("2017-03-11T02:30:00-05:00").checked_add_years(1, DayOverflow::Clamp, &tz),
+        Some(t("2018-03-11T03:30:00-04:00"))
+    );
+
+    let date = Date::new(2018, 11, 4).unwrap();
+    assert_eq!(
+        date.at(TimeOfDay::new(1, 30, 0, 0).unwrap(), &tz),
+        Some(t("2018-11-04T01:30:00-04:00"))
+    );
+    assert_eq!(
+        date.at(TimeOfDay::new(0, 0, 0, 0).unwrap(), tz.clone()),
+        Some(t("2018-11-04T00:00:00-04:00"))
+    );
+
+    // 2018-11-03T23:30:00-04:00
+    let late = t("2018-11-04T03:30:00Z");
+    assert_eq!(late.to_date(&tz), Date::new(2018, 11, 3).unwrap());
+    assert_eq!(late.time_of_day(&tz), TimeOfDay::new(23, 30, 0, 0).unwrap());
+    assert_eq!(late.weekday(&tz), Weekday::Saturday);
+    assert_eq!(late.ordinal(&tz), 307);
+    assert_eq!(
+        late.iso_week(&tz),
+        Date::new(2018, 11, 3).unwrap().iso_week()
+    );
+}
+
+#[test]
+fn test_zone_formats() {
+    let tz = PosixTz::new("EST5EDT,M3.2.0,M11.1.0").unwrap();
+    let t = |s: &str| parse_rfc3339(s).unwrap();
+
+    let summer = t("2018-07-01T12:00:00Z");
+    let winter = t("2018-12-01T12:00:00Z");
+    assert_eq!(format_rfc3339(&summer, &tz), "2018-07-01T08:00:00-04:00");
+    assert_eq!(format_rfc3339(&winter, &tz), "2018-12-01T07:00:00-05:00");
+    assert_eq!(
+        format_rfc2822(&summer, &tz),
+        Some("Sun, 01 Jul 2018 08:00:00 -0400".to_owned())
+    );
+    let f = Format::new("%F %T %z").unwrap();
+    assert_eq!(f.format(&winter, &tz), "2018-12-01 07:00:00 -0500");
+
+    let f = Format::new("%F %T").unwrap();
+    let cases = vec![
+        (
+            "2018-07-01 08:00:00",
+            Disambiguation::Reject,
+            Ok("2018-07-01T08:00:00-04:00"),
+        ),
+        (
+            "2018-03-11 02:30:00",
+            Disambiguation::Compatible,
+            Ok("2018-03-11T03:30:00-04:00"),
+        ),
+        (
+            "2018-03-11 02:30:00",
+            Disambiguation::Reject,
+            Err(ParseError::InvalidValue),
+        ),
+        (
+            "2018-11-04 01:30:00",
+            Disambiguation::Later,
+            Ok("2018-11-04T01:30:00-05:00"),
+        ),
+        (
+            "2018-11-04 01:30:00",
+            Disambiguation::Reject,
+            Err(ParseError::InvalidValue),
+        ),
+        (
+            "+99999-12-31 23:00:00",
+            Disambiguation::Compatible,
+            Err(ParseError::Overflow),
+        ),
+    ];
+    for (s, disambiguation, expect) in cases {
+        assert_eq!(f.parse_in(s, &tz, disambiguation), expect.map(t), "{}", s);
+    }
+    assert_eq!(
+        Format::new("%F %T %z").unwrap().parse_in(
+            "2018-03-11 02:30:00 +0000",
+            &tz,
+            Disambiguation::Reject
+        ),
+        Ok(t("2018-03-11T02:30:00Z"))
+    );
+
+    let received = t("2018-11-04T12:00:00Z");
+    assert_eq!(
+        parse_syslog("Nov  4 01:30:00", &received, &tz),
+        Ok(t("2018-11-04T01:30:00-04:00"))
+    );
+    assert_eq!(
+        parse_log4j("2018-03-11 02:30:00,000", &tz),
+        Ok(t("2018-03-11T03:30:00-04:00"))
+    );
+
+    let now = t("2018-03-11T12:00:00-04:00");
+    let cases = vec![
+        ("today", "2018-03-11T00:00:00-05:00"),
+        ("yesterday", "2018-03-10T00:00:00-05:00"),
+        ("tomorrow", "2018-03-12T00:00:00-04:00"),
+    ];
+    for (s, expect) in cases {
+        assert_eq!(parse_relative(s, &now, &tz), Ok(t(expect)), "{}", s);
+    }
+
+    let f = RelativeFormatter::new()
+        .absolute_after(Some(Duration::from_secs(86400)))
+        .timezone(&tz);
+    let late = t("2018-11-04T03:30:00Z");
+    assert_eq!(f.format(&late, &t("2018-11-08T00:00:00Z")), "2018-11-03");
+}
+
+#[test]
+fn test_parse_in() {
+    let zone = PosixTz::new("CST6CDT,M3.2.0,M11.1.0").unwrap();
+    let p = Rfc3339Parser::new();
+    let cases = vec![
+        ("2018-09-21 16:56:44", Ok("2018-09-21T16:56:44-05:00")),
+        ("2018-09-21", Ok("2018-09-21T00:00:00-05:00")),
+        ("2018-12-21T16:56:44.5", Ok("2018-12-21T16:56:44.5-06:00")),
+        ("2018-09-21T16:56:44+08:00", Ok("2018-09-21T16:56:44+08:00")),
+        ("2018-09-21T16:56:44Z", Ok("2018-09-21T16:56:44Z")),
+        ("2018-03-11T02:30:00", Ok("2018-03-11T03:30:00-05:00")),
+        ("2018-11-04T01:30:00", Ok("2018-11-04T01:30:00-05:00")),
+        ("+99999-12-31T23:00:00", Err(ParseError::Overflow)),
+        ("2018-09-21T16:56", Ok("2018-09-21T16:56:00-05:00")),
+        ("2018-09-21T25:56:44", Err(ParseError::Overflow)),
+        ("2016-12-31T17:59:60", Ok("2016-12-31T23:59:60Z")),
+        ("2016-12-31T23:59:60", Err(ParseError::Overflow)),
+        ("2016-12-31T23:59:60Z", Ok("2016-12-31T23:59:60Z")),
+    ];
+
+    for (s, expect) in cases {
+        assert_eq!(
+            p.parse_in(s, &zone, Disambiguation::Compatible),
+            expect.map(|e| parse_rfc3339(e).unwrap()),
+            "{}",
+            s
+        );
+    }
+
+    for s in ["2018-03-11T02:30:00", "2018-11-04T01:30:00"].iter() {
+        assert_eq!(
+            p.parse_in(s, &zone, Disambiguation::Reject),
+            Err(ParseError::InvalidValue),
+            "{}",
+            s
+        );
+    }
+
+    let roll = Rfc3339Parser::new().leap_second(LeapSecond::Roll);
+    assert_eq!(
+        roll.parse_in("2016-12-31T17:59:60.5", &zone, Disambiguation::Compatible),
+        parse_rfc3339("2017-01-01T00:00:00.5Z")
+    );
+
+    let strict = Rfc3339Parser::new().mode(Mode::Strict);
+    assert_eq!(
+        strict.parse_in("2018-09-21T16:56:44.5", &zone, Disambiguation::Compatible),
+        Err(ParseError::InvalidTimezone)
+    );
+    let utc = TimeZone::utc();
+    assert_eq!(
+        p.parse_in("2018-09-21 16:56:44", &utc, Disambiguation::Reject),
+        parse_rfc3339("2018-09-21T16:56:44Z")
+    );
+}
+
+#[test]
+fn test_local_zone() {
+    let utc = LocalZone::Fixed(TimeZone::utc());
+    let cases = vec![
+        ("", Some(utc.clone())),
+        (
+            "CST6CDT,M3.2.0,M11.1.0",
+            Some(LocalZone::Posix(
+                PosixTz::new("CST6CDT,M3.2.0,M11.1.0").unwrap(),
+            )),
+        ),
+        (
+            "<+08>-8",
+            Some(LocalZone::Posix(PosixTz::new("<+08>-8").unwrap())),
+        ),
+        ("No/Such_Zone", None),
+        (":No/Such_Zone", None),
+        (":/no/such/file", None),
+        (":CST6CDT,M3.2.0,M11.1.0", None),
+    ];
+
+    for (s, expect) in cases {
+        assert_eq!(LocalZone::from_tz_var(s), expect, "{}", s);
+    }
+
+    let t = parse_rfc3339("2018-09-21T08:56:44Z").unwrap();
+    let zone = LocalZone::from_tz_var("<+08>-8").unwrap();
+    assert_eq!(zone.offset_at(&t), TimeZone::new(8).unwrap());
+
+    #[cfg(feature = "tzdb")]
+    {
+        if let Some(zone) = LocalZone::from_tz_var(":Asia/Shanghai") {
+            assert_eq!(zone.offset_at(&t), TimeZone::new(8).unwrap());
+            assert_eq!(LocalZone::from_tz_var("Asia/Shanghai"), Some(zone));
+        }
+    }
+
+    let now = Time::now();
+    let system = Time::from_system_time(SystemTime::now()).unwrap();
+    assert!(system.duration_since(&now).is_ok());
+    assert!(system.duration_since(&now).unwrap() < Duration::from_secs(60));
+}
+
+#[test]
+fn test_date() {
+    let cases = vec![
+        ("2018-09-21", Ok((2018, 9, 21))),
+        ("20180921", Ok((2018, 9, 21))),
+        (" 2016-02-29 ", Ok((2016, 2, 29))),
+        ("2000-02-29", Ok((2000, 2, 29))),
+        ("0000-01-01", Ok((0, 1, 1))),
+        ("9999-12-31", Ok((9999, 12, 31))),
+        ("", Err(ParseError::EmptyInput)),
+        ("2018-9-21", Err(ParseError::Malformed)),
+        ("2018-09", Err(ParseError::TooShort)),
+        ("2018-09-21T00", Err(ParseError::TooLong)),
+        ("2018/09/21", Err(ParseError::Malformed)),
+        ("2018-0a-21", Err(ParseError::InvalidValue)),
+        ("2018-13-01", Err(ParseError::Overflow)),
+        ("2018-00-01", Err(ParseError::Overflow)),
+        ("2018-09-00", Err(ParseError::Overflow)),
+        ("2018-09-31", Err(ParseError::Overflow)),
+        ("2018-02-29", Err(ParseError::Overflow)),
+        ("1900-02-29", Err(ParseError::Overflow)),
+    ];
+
+    for (s, expect) in cases {
+        let expect = expect.map(|(y, m, d)| Date::new(y, m, d).unwrap());
+        assert_eq!(s.parse::<Date>(), expect, "{}", s);
+    }
+
+    let cases = vec![
+        ((2018, 9, 21), "2018-09-21", Weekday::Friday),
+        ((0, 1, 1), "0000-01-01", Weekday::Saturday),
+        ((-1, 12, 31), "-0001-12-31", Weekday::Friday),
+        ((10000, 1, 1), "+10000-01-01", Weekday::Saturday),
+    ];
+
+    for ((y, m, d), s, weekday) in cases {
+        let date = Date::new(y, m, d).unwrap();
+        assert_eq!(date.to_string(), s);
+        assert_eq!(date.weekday(), weekday, "{}", s);
+    }
+
+    assert!(Date::new(2016, 2, 29).unwrap().is_leap_year());
+    assert!(!Date::new(2100, 2, 28).unwrap().is_leap_year());
+    assert!(Date::new(2018, 9, 21) < Date::new(2018, 10, 1));
+    assert!(Date::new(-4, 2, 29).is_some());
+}
+
+#[test]
+fn test_time_of_day() {
+    let cases = vec![
+        ("16:56", Ok((16, 56, 0, 0))),
+        ("16:56:44", Ok((16, 56, 44, 0))),
+        ("16:56:44.234", Ok((16, 56, 44, 234_000_000))),
+        ("16:56:44,5", Ok((16, 56, 44, 500_000_000))),
+        ("23:59:59.9999999999", Ok((23, 59, 59, 999_999_999))),
+        ("00:00:00", Ok((0, 0, 0, 0))),
+        ("", Err(ParseError::EmptyInput)),
+        ("16:5", Err(ParseError::TooShort)),
+        ("16-56", Err(ParseError::Malformed)),
+        ("16:56:4", Err(ParseError::Malformed)),
+        ("16:56:44Z", Err(ParseError::Malformed)),
+        ("16:56:44.", Err(ParseError::MissingValue)),
+        ("16:56:44.5x", Err(ParseError::InvalidValue)),
+        ("1a:56", Err(ParseError::InvalidValue)),
+        ("24:00:00", Err(ParseError::Overflow)),
+        ("23:60:00", Err(ParseError::Overflow)),
+        ("23:59:60", Err(ParseError::Overflow)),
+    ];
+
+    for (s, expect) in cases {
+        let expect = expect.map(|(h, m, sec, n)| TimeOfDay::new(h, m, sec, n).unwrap());
+        assert_eq!(s.parse::<TimeOfDay>(), expect, "{}", s);
+    }
+
+    let cases = vec![
+        ((16, 56, 44, 0), "16:56:44"),
+        ((16, 56, 44, 234_000_000), "16:56:44.234"),
+        ((0, 0, 0, 1), "00:00:00.000000001"),
+    ];
+
+    for ((h, m, sec, n), s) in cases {
+        assert_eq!(TimeOfDay::new(h, m, sec, n).unwrap().to_string(), s);
+    }
+
+    assert_eq!(TimeOfDay::new(0, 0, 0, 0), Some(TimeOfDay::MIDNIGHT));
+    assert_eq!(TimeOfDay::default(), TimeOfDay::MIDNIGHT);
+    assert_eq!(TimeOfDay::new(0, 0, 0, 1_000_000_000), None);
+}
+
+#[test]
+fn test_date_time_combine() {
+    let tz = TimeZone::new(8).unwrap();
+    let time = TimeOfDay::new(16, 56, 44, 234_000_000).unwrap();
+    let cases = vec![
+        (
+            Date::new(2018, 9, 21).unwrap(),
+            time,
+            tz,
+            parse_rfc3339("2018-09-21T16:56:44.234+08:00").ok(),
+        ),
+        (
+            Date::new(-99999, 1, 1).unwrap(),
+            TimeOfDay::MIDNIGHT,
+            tz,
+            None,
+        ),
+        (
+            Date::new(-99999, 1, 1).unwrap(),
+            TimeOfDay::new(8, 0, 0, 0).unwrap(),
+            tz,
+            parse_rfc3339("-99999-01-01T00:00:00Z").ok(),
+        ),
+        (
+            Date::new(99999, 12, 31).unwrap(),
+            TimeOfDay::new(23, 0, 0, 0).unwrap(),
+            TimeZone::new(-12).unwrap(),
+            None,
+        ),
+    ];
+
+    for (date, time, tz, expect) in cases {
+        assert_eq!(date.at(time, tz), expect, "{} {}", date, time);
+    }
+
+    let t = parse_rfc3339("2018-09-21T20:56:44.234Z").unwrap();
+    assert_eq!(t.to_date(tz), Date::new(2018, 9, 22).unwrap());
+    assert_eq!(
+        t.time_of_day(tz),
+        TimeOfDay::new(4, 56, 44, 234_000_000).unwrap()
+    );
+    assert_eq!(t.to_date(tz).at(t.time_of_day(tz), tz), Some(t));
+}
+
+#[test]
+fn test_calendar_arithmetic() {
+    let cases = vec![
+        ((2018, 1, 31), 1, DayOverflow::Clamp, Some((2018, 2, 28))),
+        ((2018, 1, 31), 1, DayOverflow::Roll, Some((2018, 3, 3))),
+        ((2018, 1, 31), 1, DayOverflow::Reject, None),
+        ((2016, 1, 31), 1, DayOverflow::Clamp, Some((2016, 2, 29))),
+        ((2016, 1, 31), 1, DayOverflow::Roll, Some((2016, 3, 2))),
+        ((2018, 3, 31), -1, DayOverflow::Clamp, Some((2018, 2, 28))),
+        ((2018, 3, 31), 1, DayOverflow::Clamp, Some((2018, 4, 30))),
+        ((2018, 5, 31), 1, DayOverflow::Roll, Some((2018, 7, 1))),
+        ((2018, 1, 15), 1, DayOverflow::Reject, Some((2018, 2, 15))),
+        ((2018, 12, 15), 1, DayOverflow::Reject, Some((2019, 1, 15))),
+        ((2018, 1, 15), -1, DayOverflow::Reject, Some((2017, 12, 15))),
+        (
+            (2018, 1, 15),
+            -13,
+            DayOverflow::Reject,
+            Some((2016, 12, 15)),
+        ),
+        ((2018, 1, 15), 25, DayOverflow::Reject, Some((2020, 2, 15))),
+        ((2018, 9, 21), 0, DayOverflow::Reject, Some((2018, 9, 21))),
+        ((0, 1, 1), -1, DayOverflow::Reject, Some((-1, 12, 1))),
+        ((2018, 1, 15), i64::MAX, DayOverflow::Reject, None),
+        ((2018, 1, 15), i64::MIN, DayOverflow::Reject, None),
+    ];
+
+    for ((y, m, d), months, policy, expect) in cases {
+        let date = Date::new(y, m, d).unwrap();
+        let expect = expect.map(|(y, m, d)| Date::new(y, m, d).unwrap());
+        assert_eq!(
+            date.checked_add_months(months, policy),
+            expect,
+            "{} {} {:?}",
+            date,
+            months,
+            policy
+        );
+    }
+
+    let leap_day = Date::new(2016, 2, 29).unwrap();
+    let cases = vec![
+        (1, DayOverflow::Clamp, Date::new(2017, 2, 28)),
+        (1, DayOverflow::Roll, Date::new(2017, 3, 1)),
+        (1, DayOverflow::Reject, None),
+        (4, DayOverflow::Reject, Date::new(2020, 2, 29)),
+        (-100, DayOverflow::Clamp, Date::new(1916, 2, 29)),
+        (84, DayOverflow::Clamp, Date::new(2100, 2, 28)),
+        (i64::MAX, DayOverflow::Clamp, None),
+    ];
+
+    for (years, policy, expect) in cases {
+        assert_eq!(
+            leap_day.checked_add_years(years, policy),
+            expect,
+            "{} {:?}",
+            years,
+            policy
+        );
+    }
+
+    let date = Date::new(2018, 9, 21).unwrap();
+    let cases = vec![
+        (0, Date::new(2018, 9, 21)),
+        (10, Date::new(2018, 10, 1)),
+        (-264, Date::new(2017, 12, 31)),
+        (365, Date::new(2019, 9, 21)),
+        (i64::MAX, None),
+    ];
+
+    for (days, expect) in cases {
+        assert_eq!(date.checked_add_days(days), expect, "{}", days);
+    }
+
+    let t = parse_rfc3339("2018-01-31T16:56:44.5+08:00").unwrap();
+    let tz = TimeZone::new(8).unwrap();
+    let cases = vec![
+        (
+            t.checked_add_months(1, DayOverflow::Clamp, tz),
+            parse_rfc3339("2018-02-28T16:56:44.5+08:00").ok(),
+        ),
+        (
+            t.checked_add_months(-1, DayOverflow::Reject, tz),
+            parse_rfc3339("2017-12-31T16:56:44.5+08:00").ok(),
+        ),
+        (t.checked_add_months(1, DayOverflow::Reject, tz), None),
+        (
+            t.checked_add_years(-1, DayOverflow::Reject, tz),
+            parse_rfc3339("2017-01-31T16:56:44.5+08:00").ok(),
+        ),
+        (
+            t.checked_add_days(1, tz),
+            parse_rfc3339("2018-02-01T16:56:44.5+08:00").ok(),
+        ),
+        (t.checked_add_years(98000, DayOverflow::Clamp, tz), None),
+        (t.checked_add_years(-102018, DayOverflow::Clamp, tz), None),
+    ];
+
+    for (i, (result, expect)) in cases.into_iter().enumerate() {
+        assert_eq!(result, expect, "case {}", i);
+    }
+}
+
+#[test]
+fn test_week_date() {
+    // (date, week date, ordinal date)
+    let cases = vec![
+        ((2018, 9, 21), "2018-W38-5", "2018-264"),
+        ((2005, 1, 1), "2004-W53-6", "2005-001"),
+        ((2005, 1, 2), "2004-W53-7", "2005-002"),
+        ((2005, 12, 31), "2005-W52-6", "2005-365"),
+        ((2007, 1, 1), "2007-W01-1", "2007-001"),
+        ((2007, 12, 30), "2007-W52-7", "2007-364"),
+        ((2007, 12, 31), "2008-W01-1", "2007-365"),
+        ((2008, 12, 28), "2008-W52-7", "2008-363"),
+        ((2008, 12, 29), "2009-W01-1", "2008-364"),
+        ((2008, 12, 31), "2009-W01-3", "2008-366"),
+        ((2009, 12, 31), "2009-W53-4", "2009-365"),
+        ((2010, 1, 3), "2009-W53-7", "2010-003"),
+        ((2020, 12, 31), "2020-W53-4", "2020-366"),
+        ((0, 1, 1), "-0001-W52-6", "0000-001"),
+        ((-44, 3, 15), "-0044-W11-4", "-0044-075"),
+        ((12345, 3, 15), "+12345-W11-4", "+12345-074"),
+    ];
+
+    for ((y, m, d), week_date, ordinal_date) in cases {
+        let date = Date::new(y, m, d).unwrap();
+        assert_eq!(format_week_date(&date), week_date, "{}", date);
+        assert_eq!(format_ordinal_date(&date), ordinal_date, "{}", date);
+        assert_eq!(parse_week_date(week_date), Ok(date), "{}", week_date);
+        assert_eq!(
+            parse_ordinal_date(ordinal_date),
+            Ok(date),
+            "{}",
+            ordinal_date
+        );
+    }
+
+    let cases = vec![
+        ("2018W385", Ok((2018, 9, 21))),
+        ("2004-W53-6", Ok((2005, 1, 1))),
+        ("", Err(ParseError::EmptyInput)),
+        ("2018W38", Err(ParseError::TooShort)),
+        ("2018-W38", Err(ParseError::Malformed)),
+        ("2018-W38-5T", Err(ParseError::TooLong)),
+        ("2018-w38-5", Err(ParseError::Malformed)),
+        ("2018-W385", Err(ParseError::Malformed)),
+        ("2018-Wx8-5", Err(ParseError::InvalidValue)),
+        ("2018-W00-1", Err(ParseError::Overflow)),
+        ("2018-W53-1", Err(ParseError::Overflow)),
+        ("2018-W38-0", Err(ParseError::Overflow)),
+        ("2018-W38-8", Err(ParseError::Overflow)),
+        ("-44-W11-4", Err(ParseError::Malformed)),
+        ("+12345W114", Err(ParseError::Malformed)),
+        ("+12345-W114", Err(ParseError::Malformed)),
+        ("+100000-W01-1", Err(ParseError::Overflow)),
+    ];
+
+    for (s, expect) in cases {
+        let expect = expect.map(|(y, m, d)| Date::new(y, m, d).unwrap());
+        assert_eq!(parse_week_date(s), expect, "{}", s);
+    }
+
+    let cases = vec![
+        ("2018264", Ok((2018, 9, 21))),
+        ("2016-366", Ok((2016, 12, 31))),
+        ("", Err(ParseError::EmptyInput)),
+        ("2018-2", Err(ParseError::TooShort)),
+        ("2018-26", Err(ParseError::InvalidValue)),
+        ("2018-2641", Err(ParseError::TooLong)),
+        ("2018/264", Err(ParseError::Malformed)),
+        ("2018-2x4", Err(ParseError::InvalidValue)),
+        ("2018-000", Err(ParseError::Overflow)),
+        ("2018-366", Err(ParseError::Overflow)),
+        ("+12345074", Err(ParseError::Malformed)),
+        ("-0044-75", Err(ParseError::Malformed)),
+        ("-0043-366", Err(ParseError::Overflow)),
+    ];
+
+    for (s, expect) in cases {
+        let expect = expect.map(|(y, m, d)| Date::new(y, m, d).unwrap());
+        assert_eq!(parse_ordinal_date(s), expect, "{}", s);
+    }
+
+    // the first and last dates
+    let cases = vec![
+        ((-99999, 1, 1), "-99999-W01-1"),
+        ((99996, 12, 31), "+99997-W01-2"),
+        ((99999, 12, 31), "+99999-W52-5"),
+    ];
+    for ((y, m, d), week_date) in cases {
+        let date = Date::new(y, m, d).unwrap();
+        assert_eq!(format_week_date(&date), week_date, "{}", date);
+        assert_eq!(parse_week_date(week_date), Ok(date), "{}", week_date);
+    }
+    assert_eq!(Date::new(i32::MAX, 12, 31), None);
+    assert_eq!(Date::new(i32::MIN, 1, 1), None);
+    assert_eq!(Date::new(100000, 1, 1), None);
+    assert_eq!(Date::from_iso_week(99999, 53, Weekday::Monday), None);
+
+    let t = parse_rfc3339("2018-12-30T20:00:00Z").unwrap();
+    let tz = TimeZone::new(8).unwrap();
+    assert_eq!(t.weekday(TimeZone::utc()), Weekday::Sunday);
+    assert_eq!(t.weekday(tz), Weekday::Monday);
+    assert_eq!(t.ordinal(TimeZone::utc()), 364);
+    assert_eq!(t.ordinal(tz), 365);
+    assert_eq!(t.iso_week(TimeZone::utc()).to_string(), "2018-W52");
+    let week = t.iso_week(tz);
+    assert_eq!((week.year(), week.week()), (2019, 1));
+
+    for n in 1..8 {
+        assert_eq!(Weekday::from_iso_number(n).unwrap().iso_number(), n);
+    }
+    assert_eq!(Weekday::from_iso_number(0), None);
+    assert_eq!(Weekday::from_iso_number(8), None);
+    assert_eq!(Weekday::Monday.iso_number(), 1);
+    assert_eq!(Weekday::Sunday.iso_number(), 7);
+}
+
+#[test]
+fn test_format_rfc3339() {
+    let cases = vec![
+        ("2018-09-21T16:56:44Z", 0, "2018-09-21T16:56:44Z"),
+        (
+            "2018-09-21T16:56:44.234Z",
+            8 * 3600,
+            "2018-09-22T00:56:44.234+08:00",
+        ),
+        (
+            "2018-09-21T16:56:44.000000001Z",
+            -(5 * 3600 + 1800),
+            "2018-09-21T11:26:44.000000001-05:30",
+        ),
+        ("0000-01-01T00:00:00Z", 0, "0000-01-01T00:00:00Z"),
+        (
+            "9999-12-31T23:59:59.999999999Z",
+            0,
+            "9999-12-31T23:59:59.999999999Z",
+        ),
+        // the seconds of an offset are dropped, also from the local time
+        (
+            "1900-01-01T00:00:00Z",
+            8 * 3600 + 343,
+            "1900-01-01T08:05:00+08:05",
+        ),
+        (
+            "2018-09-21T16:56:44Z",
+            5 * 3600 + 1800 + 15,
+            "2018-09-21T22:26:44+05:30",
+        ),
+        (
+            "2018-09-21T16:56:44Z",
+            -(5 * 3600 + 1800 + 15),
+            "2018-09-21T11:26:44-05:30",
+        ),
+        ("2018-09-21T16:56:44Z", 59, "2018-09-21T16:56:44Z"),
+    ];
+
+    for (s, offset, expected) in cases {
+        let t = parse_rfc3339(s).unwrap();
+        let tz = TimeZone::from_offset(offset).unwrap();
+        assert_eq!(format_rfc3339(&t, tz), expected, "{}", s);
+        assert_eq!(parse_rfc3339(expected), Ok(t), "{}", s);
+    }
+}
+
+#[test]
+fn test_interval() {
+    let t = |s: &str| parse_rfc3339(s).unwrap();
+    let day = Interval::new(t("2018-09-21T00:00:00Z"), t("2018-09-22T00:00:00Z")).unwrap();
+
+    let cases = vec![
+        "2018-09-21T00:00:00Z/2018-09-22T00:00:00Z",
+        "2018-09-21T08:00:00+08:00/2018-09-22T00:00Z",
+        "2018-09-21T00:00Z/P1D",
+        "2018-09-21T00:00Z/PT24H",
+        "P1D/2018-09-22T00:00Z",
+        "PT23H60M/2018-09-22T00:00Z",
+        "2018-09-21T00:00Z..2018-09-22T00:00Z",
+        " 2018-09-21 00:00:00..2018-09-22 ",
+    ];
+
+    for s in cases {
+        assert_eq!(
+            s.parse::<Interval>(),
+            Ok(Interval::new(t("2018-09-21T00:00:00Z"), t("2018-09-22T00:00:00Z")).unwrap()),
+            "{}",
+            s
+        );
+    }
+
+    assert_eq!(day.to_string(), "2018-09-21T00:00:00Z/2018-09-22T00:00:00Z");
+    assert_eq!(day.duration(), Duration::from_secs(86400));
+    assert!(!day.is_empty());
+    assert!(day.contains(&t("2018-09-21T00:00:00Z")));
+    assert!(day.contains(&t("2018-09-21T23:59:59.999999999Z")));
+    assert!(!day.contains(&t("2018-09-22T00:00:00Z")));
+    assert!(!day.contains(&t("2018-09-20T23:59:59Z")));
+
+    let empty = Interval::new(t("2018-09-21T12:00:00Z"), t("2018-09-21T12:00:00Z")).unwrap();
+    assert!(empty.is_empty());
+    assert!(!empty.contains(&t("2018-09-21T12:00:00Z")));
+    assert!(!day.overlaps(&empty));
+    assert_eq!(
+        Interval::new(t("2018-09-22T00:00:00Z"), t("2018-09-21T00:00:00Z")),
+        None
+    );
+
+    let evening = "2018-09-21T18:00Z/PT12H".parse::<Interval>().unwrap();
+    let next_day = "2018-09-22T00:00Z/P1D".parse::<Interval>().unwrap();
+    assert!(day.overlaps(&evening));
+    assert!(evening.overlaps(&day));
+    assert!(!day.overlaps(&next_day));
+    assert_eq!(day.intersection(&next_day), None);
+    assert_eq!(
+        day.intersection(&evening),
+        "2018-09-21T18:00Z/PT6H".parse().ok()
+    );
+    assert_eq!(evening.intersection(&day), day.intersection(&evening));
+    assert_eq!(
+        next_day.intersection(&evening),
+        "2018-09-22T00:00Z/PT6H".parse().ok()
+    );
+
+    let calendar = vec![
+        (
+            "2018-09-21T00:00Z/P1Y",
+            "2018-09-21T00:00:00Z/2019-09-21T00:00:00Z",
+        ),
+        (
+            "2018-01-31T00:00Z/P1M",
+            "2018-01-31T00:00:00Z/2018-02-28T00:00:00Z",
+        ),
+        (
+            "2016-02-29T00:00Z/P1Y",
+            "2016-02-29T00:00:00Z/2017-02-28T00:00:00Z",
+        ),
+        (
+            "2018-09-21T08:00+08:00/P1Y2M10DT2H",
+            "2018-09-21T00:00:00Z/2019-12-01T02:00:00Z",
+        ),
+        (
+            "P1M/2018-03-31T00:00Z",
+            "2018-02-28T00:00:00Z/2018-03-31T00:00:00Z",
+        ),
+        (
+            "P1Y2M10DT2H/2019-12-01T02:00Z",
+            "2018-09-21T00:00:00Z/2019-12-01T02:00:00Z",
+        ),
+        (
+            "P0Y/2018-09-21T00:00Z",
+            "2018-09-21T00:00:00Z/2018-09-21T00:00:00Z",
+        ),
+    ];
+    for (s, expected) in calendar {
+        assert_eq!(
+            s.parse::<Interval>().map(|i| i.to_string()),
+            Ok(expected.to_string()),
+            "{}",
+            s
+        );
+    }
+
+    let steps: Vec<Time> = day.steps(Duration::from_secs(7 * 3600)).collect();
+    assert_eq!(
+        steps,
+        vec![
+            t("2018-09-21T00:00:00Z"),
+            t("2018-09-21T07:00:00Z"),
+            t("2018-09-21T14:00:00Z"),
+            t("2018-09-21T21:00:00Z"),
+        ]
+    );
+    assert_eq!(empty.steps(Duration::from_secs(1)).count(), 0);
+    assert_eq!(day.steps(Duration::from_secs(86400)).count(), 1);
+
+    // stops at the end of the range of time
+    let last = "+99999-12-31T23:59:59Z..+99999-12-31T23:59:59.5Z"
+        .parse::<Interval>()
+        .unwrap();
+    assert_eq!(last.steps(Duration::from_millis(300)).count(), 2);
+}
+
+#[test]
+fn test_interval_errors() {
+    let cases = vec![
+        ("", ParseError::EmptyInput),
+        ("2018-09-21T00:00Z", ParseError::Malformed),
+        ("2018-09-21T00:00Z/", ParseError::MissingValue),
+        ("/2018-09-21T00:00Z", ParseError::MissingValue),
+        ("..2018-09-21T00:00Z", ParseError::MissingValue),
+        ("P1D/P1D", ParseError::TooShort),
+        ("2018-09-21T00:00Z/P1.5Y", ParseError::InvalidUnit),
+        ("2018-09-21T00:00Z/P1M1Y", ParseError::Malformed),
+        ("2018-09-21T00:00Z/P1Y1Y", ParseError::DuplicateUnit),
+        ("2018-09-21T00:00Z/P1YT", ParseError::MissingValue),
+        ("+99999-12-01T00:00Z/P1M", ParseError::Overflow),
+        ("P1Y/-99999-06-01T00:00Z", ParseError::Overflow),
+        (
+            "2018-09-22T00:00Z/2018-09-21T00:00Z",
+            ParseError::InvalidValue,
+        ),
+        (
+            "2018-09-22T00:00Z..2018-09-21T00:00Z",
+            ParseError::InvalidValue,
+        ),
+        ("+99999-12-31T00:00Z/P1D", ParseError::Overflow),
+        ("P1D/-99999-01-01T00:00Z", ParseError::Overflow),
+    ];
+
+    for (s, expected) in cases {
+        assert_eq!(s.parse::<Interval>(), Err(expected), "{}", s);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_interval_zero_step() {
+    let day = "2018-09-21T00:00Z/P1D".parse::<Interval>().unwrap();
+    day.steps(Duration::new(0, 0));
+}
+
+#[test]
+fn test_expanded_year() {
+    assert_eq!(days_from_civil(-99999, 1, 1) * 86400, MIN_SECONDS);
+    assert_eq!(days_from_civil(100000, 1, 1) * 86400, MAX_SECONDS);
+
+    let cases = vec![
+        ("+012345-01-01", (12345, 1, 1), "+12345-01-01T00:00:00Z"),
+        ("-0044-03-15", (-44, 3, 15), "-0044-03-15T00:00:00Z"),
+        ("-0044-03-15T12:00Z", (-44, 3, 15), "-0044-03-15T12:00:00Z"),
+        (
+            "+10000-01-01T00:00:00+01:00",
+            (9999, 12, 31),
+            "9999-12-31T23:00:00Z",
+        ),
+        (
+            "-0001-12-31T23:59:59.5Z",
+            (-1, 12, 31),
+            "-0001-12-31T23:59:59.5Z",
+        ),
+        ("+0000-02-29", (0, 2, 29), "0000-02-29T00:00:00Z"),
+        ("-0000-01-01", (0, 1, 1), "0000-01-01T00:00:00Z"),
+        ("-0004-02-29", (-4, 2, 29), "-0004-02-29T00:00:00Z"),
+        (
+            "-99999-01-01T00:00:00Z",
+            (-99999, 1, 1),
+            "-99999-01-01T00:00:00Z",
+        ),
+        (
+            "+099999-12-31T23:59:59.999999999Z",
+            (99999, 12, 31),
+            "+99999-12-31T23:59:59.999999999Z",
+        ),
+    ];
+
+    for (s, (year, month, day), formatted) in cases {
+        let t = parse_rfc3339(s).unwrap();
+        let c = t.components(TimeZone::utc());
+        assert_eq!((c.year, c.month, c.day), (year, month, day), "{}", s);
+        assert_eq!(format_rfc3339(&t, TimeZone::utc()), formatted, "{}", s);
+        assert_eq!(parse_rfc3339(formatted), Ok(t), "{}", s);
+    }
+
+    assert_eq!(
+        "-0044-03-15".parse::<Date>(),
+        Ok(Date::new(-44, 3, 15).unwrap())
+    );
+    assert_eq!(
+        "+012345-01-01".parse::<Date>(),
+        Ok(Date::new(12345, 1, 1).unwrap())
+    );
+    assert_eq!(
+        "-099999-01-01".parse::<Date>(),
+        Ok(Date::new(-99999, 1, 1).unwrap())
+    );
+    assert_eq!("-999999-01-01".parse::<Date>(), Err(ParseError::Overflow));
+    assert_eq!(Date::new(-44, 3, 15).unwrap().to_string(), "-0044-03-15");
+
+    let t = parse_rfc3339("-0044-03-15T12:00:00Z").unwrap();
+    assert_eq!(
+        Format::new("%Y-%m-%d %H")
+            .unwrap()
+            .format(&t, TimeZone::utc()),
+        "-0044-03-15 12"
+    );
+    let f = Format::new("%FT%T%z").unwrap();
+    for s in ["-0044-03-15T12:00:00Z", "+12345-01-01T00:00:00Z"].iter() {
+        let t = parse_rfc3339(s).unwrap();
+        assert_eq!(f.parse(&f.format(&t, TimeZone::utc())), Ok(t), "{}", s);
+    }
+
+    let cases = vec![
+        ("+100000-01-01", ParseError::Overflow),
+        ("-100000-01-01T00:00:00Z", ParseError::Overflow),
+        ("+99999-12-31T23:00:00-01:00", ParseError::Overflow),
+        ("-0005-02-29", ParseError::Overflow),
+        ("+123-01-01", ParseError::Malformed),
+        ("+1234567-01-01", ParseError::Malformed),
+        ("+0123450101", ParseError::Malformed),
+        ("-", ParseError::Malformed),
+        ("+2018-9-21", ParseError::TooShort),
+    ];
+
+    for (s, err) in cases {
+        assert_eq!(parse_rfc3339(s), Err(err), "{}", s);
+    }
+
+    let cases = vec![
+        ("+1234567-01-01", ParseError::Malformed),
+        ("-0044-03-15T00:00", ParseError::Malformed),
+        ("-0044-3-15", ParseError::Malformed),
+        ("-0044-02-30", ParseError::Overflow),
+    ];
+
+    for (s, err) in cases {
+        assert_eq!(s.parse::<Date>(), Err(err), "{}", s);
+    }
+
+    // RFC3339 has only 4-digit years
+    let strict = Rfc3339Parser::new().mode(Mode::Strict);
+    assert!(strict.parse("+2018-09-21T16:56:44Z").is_err());
+}
+
+#[test]
+fn test_time_bounds() {
+    assert_eq!(Time::MIN, parse_rfc3339("-99999-01-01T00:00:00Z").unwrap());
+    assert_eq!(
+        Time::MAX,
+        parse_rfc3339("+99999-12-31T23:59:59.999999999Z").unwrap()
+    );
+    assert_eq!(Time::MIN.sec, MIN_SECONDS);
+    assert_eq!(Time::MAX.sec, MAX_SECONDS - 1);
+    assert_eq!(Time::default(), UNIX_EPOCH);
+
+    let one = Duration::new(0, 1);
+    assert_eq!(Time::MAX.checked_add(one), None);
+    assert_eq!(Time::MIN.checked_sub(one), None);
+    assert_eq!(
+        Time::MAX.checked_sub(one),
+        parse_rfc3339("+99999-12-31T23:59:59.999999998Z").ok()
+    );
+    assert_eq!(
+        Time::MIN.checked_add(one),
+        parse_rfc3339("-99999-01-01T00:00:00.000000001Z").ok()
+    );
+    assert_eq!(
+        Time::MIN.checked_add(Time::MAX.duration_since(&Time::MIN).unwrap()),
+        Some(Time::MAX)
+    );
+    assert_eq!(
+        Time::from_timestamp(Time::MAX.timestamp(), 999_999_999),
+        Some(Time::MAX)
+    );
+    assert_eq!(Time::from_timestamp(Time::MAX.timestamp() + 1, 0), None);
+    assert_eq!(
+        Time::from_timestamp(Time::MIN.timestamp(), 0),
+        Some(Time::MIN)
+    );
+    assert_eq!(
+        Time::from_timestamp(Time::MIN.timestamp() - 1, 999_999_999),
+        None
+    );
+    assert_eq!(Time::MAX.timestamp_nanos(), None);
+    assert_eq!(
+        Time::MAX.components(TimeZone::new(-12).unwrap()).year,
+        99999
+    );
+    assert_eq!(
+        Time::MIN.truncate(Unit::Day, TimeZone::new(-1).unwrap()),
+        None
+    );
+    assert_eq!(Time::MAX.round(Unit::Second, TimeZone::utc()), None);
+
+    let cases = vec![
+        (
+            Time::MAX,
+            Duration::new(0, 0),
+            Time::MAX,
+            Time::MAX.checked_sub(Duration::new(0, 0)).unwrap(),
+        ),
+        (
+            Time::MAX,
+            one,
+            Time::MAX,
+            Time::MAX.checked_sub(one).unwrap(),
+        ),
+        (
+            Time::MIN,
+            one,
+            Time::MIN.checked_add(one).unwrap(),
+            Time::MIN,
+        ),
+        (
+            UNIX_EPOCH,
+            Duration::from_secs(u64::MAX),
+            Time::MAX,
+            Time::MIN,
+        ),
+        (
+            UNIX_EPOCH,
+            Duration::new(u64::MAX, 999_999_999),
+            Time::MAX,
+            Time::MIN,
+        ),
+        (
+            UNIX_EPOCH,
+            Duration::from_secs(i64::MAX as u64 + 1),
+            Time::MAX,
+            Time::MIN,
+        ),
+        (
+            UNIX_EPOCH,
+            Duration::from_secs(60),
+            parse_rfc3339("1970-01-01T00:01:00Z").unwrap(),
+            parse_rfc3339("1969-12-31T23:59:00Z").unwrap(),
+        ),
+    ];
+
+    for (t, d, add, sub) in cases {
+        assert_eq!(t.saturating_add(d), add, "{:?} + {:?}", t, d);
+        assert_eq!(t.saturating_sub(d), sub, "{:?} - {:?}", t, d);
+    }
+
+    assert_eq!(
+        Time::MAX.saturating_duration_since(&Time::MIN),
+        Time::MAX.duration_since(&Time::MIN).unwrap()
+    );
+    assert_eq!(
+        Time::MIN.saturating_duration_since(&Time::MAX),
+        Duration::new(0, 0)
+    );
+
+    let far = Duration::from_secs(1 << 50);
+    let cases = vec![
+        (SystemTime::UNIX_EPOCH, UNIX_EPOCH),
+        (
+            SystemTime::UNIX_EPOCH + Duration::new(1, 5),
+            Time::from_timestamp(1, 5).unwrap(),
+        ),
+        (SystemTime::UNIX_EPOCH + far, Time::MAX),
+        (SystemTime::UNIX_EPOCH - far, Time::MIN),
+    ];
+
+    for (st, expected) in cases {
+        assert_eq!(Time::saturating_from_system_time(st), expected, "{:?}", st);
+    }
+
+    // copied times are usable as map keys
+    let t = parse_rfc3339("2018-09-21T16:56:44Z").unwrap();
+    let mut seen = HashMap::new();
+    for s in &[
+        "2018-09-21T16:56:44Z",
+        "2018-09-22T00:56:44+08:00",
+        "2018-09-21T16:56:45Z",
+    ] {
+        *seen.entry(parse_rfc3339(s).unwrap()).or_insert(0) += 1;
+    }
+    assert_eq!(seen.get(&t), Some(&2));
+    assert_eq!(seen.len(), 2);
+
+    let copied = t;
+    assert_eq!(
+        copied.checked_add(Duration::from_secs(1)),
+        t.checked_add(Duration::from_secs(1))
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono() {
+    let cases = vec![
+        (
+            "2018-09-22T00:56:44.234867232+08:00",
+            1537549004,
+            234867232,
+            8 * 3600,
+        ),
+        ("1970-01-01T00:00:00Z", 0, 0, 0),
+        ("1969-12-31T23:29:59.5-00:30", -1, 500_000_000, -1800),
+        ("-0044-03-15T12:00:00Z", -63549316800, 0, 0),
+        ("-99999-01-01T00:00:00Z", -3217830796800, 0, 0),
+        (
+            "+99999-12-31T23:59:59.999999999Z",
+            3093527980799,
+            999_999_999,
+            0,
+        ),
+    ];
+
+    for (s, secs, nano, offset) in cases {
+        let t = parse_rfc3339(s).unwrap();
+        let tz = TimeZone::from_offset(offset).unwrap();
+        let utc = DateTime::from_timestamp(secs, nano).unwrap();
+        let dt = utc.with_timezone(&FixedOffset::east_opt(offset).unwrap());
+
+        assert_eq!(Time::try_from(dt), Ok(t), "{}", s);
+        assert_eq!(Time::try_from(utc), Ok(t), "{}", s);
+        assert_eq!(DateTime::<Utc>::from(t), utc, "{}", s);
+        assert_eq!(DateTime::<FixedOffset>::from(t), dt, "{}", s);
+        assert_eq!(TimeZone::from(*dt.offset()), tz, "{}", s);
+
+        let local = DateTime::<Utc>::from(t).with_timezone(&FixedOffset::from(tz));
+        assert_eq!((local, local.offset()), (dt, dt.offset()), "{}", s);
+    }
+
+    for &offset in &[0, 8 * 3600, -(5 * 3600 + 1800), 86399, -86399] {
+        let tz = TimeZone::from_offset(offset).unwrap();
+        assert_eq!(FixedOffset::from(tz).local_minus_utc(), offset);
+        assert_eq!(TimeZone::from(FixedOffset::from(tz)), tz);
+    }
+
+    let leap = DateTime::parse_from_rfc3339("2016-12-31T23:59:60.5Z").unwrap();
+    assert_eq!(
+        Time::try_from(leap),
+        parse_rfc3339("2016-12-31T23:59:59.999999999Z")
+    );
+
+    // +100000-01-01T00:00:00Z and -100000-12-31T23:59:59Z
+    for &secs in &[3093527980800, -3217830796801] {
+        let dt = DateTime::from_timestamp(secs, 0).unwrap();
+        assert_eq!(Time::try_from(dt), Err(ParseError::Overflow), "{}", secs);
+    }
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_time03() {
+    let cases = vec![
+        ("2018-09-21T16:56:44.234867232Z", 1537549004, 234867232),
+        ("1970-01-01T00:00:00Z", 0, 0),
+        ("1969-12-31T23:59:59.5Z", -1, 500_000_000),
+        ("-9999-01-01T00:00:00Z", -377705116800, 0),
+        ("9999-12-31T23:59:59.999999999Z", 253402300799, 999_999_999),
+    ];
+
+    for (s, secs, nano) in cases {
+        let t = parse_rfc3339(s).unwrap();
+        let dt = OffsetDateTime::from_unix_timestamp(secs)
+            .unwrap()
+            .replace_nanosecond(nano)
+            .unwrap();
+
+        assert_eq!(Time::try_from(dt), Ok(t), "{}", s);
+        assert_eq!(OffsetDateTime::try_from(t), Ok(dt), "{}", s);
+    }
+
+    let t = parse_rfc3339("2018-09-21T16:56:44+08:00").unwrap();
+    let tz = TimeZone::new(8).unwrap();
+    let dt = OffsetDateTime::try_from(t).unwrap().to_offset(tz.into());
+    assert_eq!((dt.hour(), dt.offset().whole_hours()), (16, 8));
+    assert_eq!(Time::try_from(dt), Ok(t));
+    assert_eq!(TimeZone::try_from(dt.offset()), Ok(tz));
+
+    for s in &[
+        "+10000-01-01T00:00:00Z",
+        "-10000-12-31T23:59:59Z",
+        "+99999-12-31T23:59:59Z",
+    ] {
+        let t = parse_rfc3339(s).unwrap();
+        assert_eq!(
+            OffsetDateTime::try_from(t),
+            Err(ParseError::Overflow),
+            "{}",
+            s
+        );
+    }
+
+    for &offset in &[0, 8 * 3600, -(5 * 3600 + 1800), 86399, -86399] {
+        let tz = TimeZone::from_offset(offset).unwrap();
+        assert_eq!(UtcOffset::from(tz).whole_seconds(), offset);
+        assert_eq!(TimeZone::try_from(UtcOffset::from(tz)), Ok(tz));
+    }
+
+    let offset = UtcOffset::from_hms(25, 0, 0).unwrap();
+    assert_eq!(TimeZone::try_from(offset), Err(ParseError::InvalidTimezone));
+}
@@ -0,0 +1,45 @@
+// conversions from and to the types of time 0.3, with the feature "time"
+
+use super::{Time, TimeZone};
+use std::convert::TryFrom;
+use time03::{OffsetDateTime, UtcOffset};
+use ParseError;
+
+impl From<TimeZone> for UtcOffset {
+    fn from(tz: TimeZone) -> UtcOffset {
+        // the offset is less than a day, within the 26 hours of UtcOffset
+        UtcOffset::from_whole_seconds(tz.offset()).expect("offset out of range")
+    }
+}
+
+impl TryFrom<UtcOffset> for TimeZone {
+    type Error = ParseError;
+
+    /// Converts the offset, returns `ParseError::InvalidTimezone` if it is a day or more
+    fn try_from(offset: UtcOffset) -> Result<TimeZone, ParseError> {
+        TimeZone::from_offset(offset.whole_seconds()).ok_or(ParseError::InvalidTimezone)
+    }
+}
+
+impl TryFrom<Time> for OffsetDateTime {
+    type Error = ParseError;
+
+    /// Converts the time at the offset 0, see `OffsetDateTime::to_offset` for other offsets.
+    /// Returns `ParseError::Overflow` if it is out of range, which is the years -9999 to 9999
+    /// unless the feature "large-dates" of time is enabled.
+    fn try_from(t: Time) -> Result<OffsetDateTime, ParseError> {
+        OffsetDateTime::from_unix_timestamp(t.timestamp())
+            .and_then(|dt| dt.replace_nanosecond(t.nano))
+            .or(Err(ParseError::Overflow))
+    }
+}
+
+impl TryFrom<OffsetDateTime> for Time {
+    type Error = ParseError;
+
+    /// Converts the time, the offset is dropped.
+    /// Returns `ParseError::Overflow` if it is out of range.
+    fn try_from(dt: OffsetDateTime) -> Result<Time, ParseError> {
+        Time::from_timestamp(dt.unix_timestamp(), dt.nanosecond()).ok_or(ParseError::Overflow)
+    }
+}
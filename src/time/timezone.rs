@@ -1,3 +1,4 @@
+use std::fmt;
 use std::str::FromStr;
 use ParseError;
 
@@ -13,7 +14,7 @@ const OFFSETS: [i32; 25] = [
     3600 * -4,
     3600 * -3,
     3600 * -2,
-    3600 * -1,
+    -3600,
     0,
     3600,
     3600 * 2,
@@ -29,6 +30,80 @@ const OFFSETS: [i32; 25] = [
     3600 * 12,
 ];
 
+// zone abbreviations of a single offset in seconds east of UTC
+const ABBREVIATIONS: [(&str, i32); 45] = [
+    ("UTC", 0),
+    ("UT", 0),
+    ("GMT", 0),
+    ("Z", 0),
+    ("WET", 0),
+    ("WEST", 3600),
+    ("CET", 3600),
+    ("CEST", 3600 * 2),
+    ("EET", 3600 * 2),
+    ("EEST", 3600 * 3),
+    ("MSK", 3600 * 3),
+    ("WAT", 3600),
+    ("CAT", 3600 * 2),
+    ("EAT", 3600 * 3),
+    ("SAST", 3600 * 2),
+    ("PKT", 3600 * 5),
+    ("NPT", 3600 * 5 + 45 * 60),
+    ("ICT", 3600 * 7),
+    ("WIB", 3600 * 7),
+    ("HKT", 3600 * 8),
+    ("SGT", 3600 * 8),
+    ("AWST", 3600 * 8),
+    ("JST", 3600 * 9),
+    ("KST", 3600 * 9),
+    ("ACST", 3600 * 9 + 30 * 60),
+    ("ACDT", 3600 * 10 + 30 * 60),
+    ("AEST", 3600 * 10),
+    ("AEDT", 3600 * 11),
+    ("NZST", 3600 * 12),
+    ("NZDT", 3600 * 13),
+    ("HST", 3600 * -10),
+    ("AKST", 3600 * -9),
+    ("AKDT", 3600 * -8),
+    ("PST", 3600 * -8),
+    ("PDT", 3600 * -7),
+    ("MST", 3600 * -7),
+    ("MDT", 3600 * -6),
+    ("CDT", 3600 * -5),
+    ("EST", 3600 * -5),
+    ("EDT", 3600 * -4),
+    ("ADT", 3600 * -3),
+    ("NST", -(3600 * 3 + 30 * 60)),
+    ("NDT", -(3600 * 2 + 30 * 60)),
+    ("BRT", 3600 * -3),
+    ("ART", 3600 * -3),
+];
+
+// zone abbreviations of several offsets, in the Americas, Asia and Europe
+const AMBIGUOUS_ABBREVIATIONS: [(&str, [Option<i32>; 3]); 4] = [
+    ("CST", [Some(3600 * -6), Some(3600 * 8), None]),
+    ("IST", [None, Some(3600 * 5 + 30 * 60), Some(3600)]),
+    ("BST", [None, Some(3600 * 6), Some(3600)]),
+    ("AST", [Some(3600 * -4), Some(3600 * 3), None]),
+];
+
+/// Chooses the offset of an abbreviation used by zones in several regions,
+/// see [`TimeZone::from_abbreviation`]
+///
+/// [`TimeZone::from_abbreviation`]: ./struct.TimeZone.html#method.from_abbreviation
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Ambiguity {
+    /// Rejects an ambiguous abbreviation
+    #[default]
+    Reject,
+    /// Takes the zone in the Americas
+    America,
+    /// Takes the zone in Asia
+    Asia,
+    /// Takes the zone in Europe
+    Europe,
+}
+
 /// Represents timezone in datetime string
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct TimeZone(i32);
@@ -41,50 +116,151 @@ impl TimeZone {
 
     /// Returns a timezone with the given hour offset
     pub fn new(hoffset: i32) -> Option<TimeZone> {
-        if hoffset < -12 || hoffset > 12 {
+        if !(-12..=12).contains(&hoffset) {
             return None;
         }
 
         Some(TimeZone(OFFSETS[(hoffset + 12) as usize]))
     }
 
+    /// Returns a timezone with the given offset in seconds east of UTC,
+    /// the offset must be less than 24 hours
+    pub fn from_offset(offset: i32) -> Option<TimeZone> {
+        if offset <= -86400 || offset >= 86400 {
+            return None;
+        }
+
+        Some(TimeZone(offset))
+    }
+
     /// Returns the actual offset in seconds
     pub fn offset(&self) -> i32 {
-        return self.0;
+        self.0
+    }
+
+    // returns the timezone with the seconds of the offset dropped, as it is displayed
+    pub(crate) fn whole_minutes(self) -> TimeZone {
+        TimeZone(self.0 / 60 * 60)
+    }
+
+    /// Returns the timezone of a zone abbreviation, (e.g. "UTC", "JST", "CET"),
+    /// ignoring ASCII case
+    ///
+    /// An abbreviation means a fixed offset, so a daylight saving abbreviation
+    /// such as "EDT" is its own offset rather than the zone of "EST".
+    /// The abbreviations below are used by zones in several regions,
+    /// the ambiguity chooses one of them or rejects the abbreviation:
+    ///
+    /// | Abbreviation | `America`                | `Asia`                     | `Europe`              |
+    /// |--------------|--------------------------|----------------------------|-----------------------|
+    /// | CST          | -06:00 Central Standard  | +08:00 China Standard      | rejected              |
+    /// | IST          | rejected                 | +05:30 India Standard      | +01:00 Irish Standard |
+    /// | BST          | rejected                 | +06:00 Bangladesh Standard | +01:00 British Summer |
+    /// | AST          | -04:00 Atlantic Standard | +03:00 Arabia Standard     | rejected              |
+    ///
+    /// Cuba Standard Time (CST -05:00) and Israel Standard Time (IST +02:00) are never chosen.
+    /// Returns `ParseError::InvalidTimezone` for unknown and rejected abbreviations.
+    ///
+    /// # Example
+    /// ```
+    /// use humanize_rs::time::{Ambiguity, TimeZone};
+    ///
+    /// let jst = TimeZone::from_abbreviation("JST", Ambiguity::Reject).unwrap();
+    /// assert_eq!(jst.offset(), 9 * 3600);
+    ///
+    /// assert!(TimeZone::from_abbreviation("CST", Ambiguity::Reject).is_err());
+    /// let cst = TimeZone::from_abbreviation("CST", Ambiguity::Asia).unwrap();
+    /// assert_eq!(cst.to_string(), "+08:00");
+    /// ```
+    pub fn from_abbreviation(abbr: &str, ambiguity: Ambiguity) -> Result<TimeZone, ParseError> {
+        let abbr = abbr.trim();
+        if abbr.is_empty() {
+            return Err(ParseError::EmptyInput);
+        }
+
+        if let Some(&(_, offset)) = ABBREVIATIONS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(abbr))
+        {
+            return Ok(TimeZone(offset));
+        }
+
+        let offsets = AMBIGUOUS_ABBREVIATIONS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(abbr))
+            .map(|&(_, offsets)| offsets)
+            .ok_or(ParseError::InvalidTimezone)?;
+
+        let offset = match ambiguity {
+            Ambiguity::Reject => None,
+            Ambiguity::America => offsets[0],
+            Ambiguity::Asia => offsets[1],
+            Ambiguity::Europe => offsets[2],
+        };
+
+        offset.map(TimeZone).ok_or(ParseError::InvalidTimezone)
+    }
+}
+
+impl fmt::Display for TimeZone {
+    /// Formats the timezone as a RFC3339 offset, "Z" or "±hh:mm", (e.g. "+08:00"),
+    /// the alternate form `{:#}` is "±hhmm", (e.g. "+0800", "+0000"),
+    /// and `{:+}` writes UTC as "+00:00" rather than "Z".
+    ///
+    /// The seconds of an offset are dropped, as RFC3339 offsets have none,
+    /// so the written offset parses back to the offset of whole minutes.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let offset = self.whole_minutes().0;
+        if offset == 0 && !f.alternate() && !f.sign_plus() {
+            return f.write_str("Z");
+        }
+
+        let sign = if offset < 0 { '-' } else { '+' };
+        let minutes = offset.abs() / 60;
+        let sep = if f.alternate() { "" } else { ":" };
+
+        write!(f, "{}{:02}{}{:02}", sign, minutes / 60, sep, minutes % 60)
     }
 }
 
 impl FromStr for TimeZone {
     type Err = ParseError;
+
+    /// Parses a RFC3339 offset, "Z", "z" or "±hh:mm", an empty string means UTC
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "" | "Z" | "+00:00" | "-00:00" => Ok(TimeZone(0)),
-            "-12:00" => Ok(TimeZone(OFFSETS[0])),
-            "-11:00" => Ok(TimeZone(OFFSETS[1])),
-            "-10:00" => Ok(TimeZone(OFFSETS[2])),
-            "-09:00" => Ok(TimeZone(OFFSETS[3])),
-            "-08:00" => Ok(TimeZone(OFFSETS[4])),
-            "-07:00" => Ok(TimeZone(OFFSETS[5])),
-            "-06:00" => Ok(TimeZone(OFFSETS[6])),
-            "-05:00" => Ok(TimeZone(OFFSETS[7])),
-            "-04:00" => Ok(TimeZone(OFFSETS[8])),
-            "-03:00" => Ok(TimeZone(OFFSETS[9])),
-            "-02:00" => Ok(TimeZone(OFFSETS[10])),
-            "-01:00" => Ok(TimeZone(OFFSETS[11])),
-
-            "+01:00" => Ok(TimeZone(OFFSETS[13])),
-            "+02:00" => Ok(TimeZone(OFFSETS[14])),
-            "+03:00" => Ok(TimeZone(OFFSETS[15])),
-            "+04:00" => Ok(TimeZone(OFFSETS[16])),
-            "+05:00" => Ok(TimeZone(OFFSETS[17])),
-            "+06:00" => Ok(TimeZone(OFFSETS[18])),
-            "+07:00" => Ok(TimeZone(OFFSETS[19])),
-            "+08:00" => Ok(TimeZone(OFFSETS[20])),
-            "+09:00" => Ok(TimeZone(OFFSETS[21])),
-            "+10:00" => Ok(TimeZone(OFFSETS[22])),
-            "+11:00" => Ok(TimeZone(OFFSETS[23])),
-            "+12:00" => Ok(TimeZone(OFFSETS[24])),
-            _ => Err(ParseError::InvalidTimezone),
-        }
+        parse_offset(s.as_bytes(), false)
+    }
+}
+
+// parses a timezone offset, lenient also accepts "±hhmm" and "±hh"
+pub(crate) fn parse_offset(bs: &[u8], lenient: bool) -> Result<TimeZone, ParseError> {
+    if bs.is_empty() || bs == b"Z" || bs == b"z" {
+        return Ok(TimeZone(0));
+    }
+
+    let sign = match bs[0] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return Err(ParseError::InvalidTimezone),
+    };
+
+    let (hh, mm) = match &bs[1..] {
+        [h1, h2, b':', m1, m2] => ([*h1, *h2], [*m1, *m2]),
+        [h1, h2, m1, m2] if lenient => ([*h1, *h2], [*m1, *m2]),
+        [h1, h2] if lenient => ([*h1, *h2], [b'0', b'0']),
+        _ => return Err(ParseError::InvalidTimezone),
+    };
+
+    match (read_2digits(hh), read_2digits(mm)) {
+        (Some(h), Some(m)) if h < 24 && m < 60 => Ok(TimeZone(sign * (h * 3600 + m * 60))),
+        _ => Err(ParseError::InvalidTimezone),
     }
 }
+
+fn read_2digits(bs: [u8; 2]) -> Option<i32> {
+    if !bs[0].is_ascii_digit() || !bs[1].is_ascii_digit() {
+        return None;
+    }
+
+    Some(i32::from(bs[0] - b'0') * 10 + i32::from(bs[1] - b'0'))
+}
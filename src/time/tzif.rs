@@ -0,0 +1,376 @@
+use super::{PosixTz, Time, TimeZone, Zone};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::str;
+use ParseError;
+
+// the directories searched for zoneinfo files, after the one in the environment variable TZDIR
+const ZONEINFO_DIRS: [&str; 3] = [
+    "/usr/share/zoneinfo",
+    "/usr/lib/zoneinfo",
+    "/usr/share/lib/zoneinfo",
+];
+
+const TZIF_MAGIC: &[u8] = b"TZif";
+const TZIF_HEADER_LENGTH: usize = 44;
+
+/// A named time zone of the [`IANA time zone database`], (e.g. "America/New_York"),
+/// read from a compiled [`TZif`] file
+///
+/// Times after the last transition of the file follow the POSIX TZ string in its footer,
+/// or keep the offset of the last transition without one. Leap seconds in the file, as in the "right/" zones, are ignored.
+///
+/// # Example
+/// ```no_run
+/// use humanize_rs::time::{parse_rfc3339, Disambiguation, Tz, Zone};
+///
+/// let tz = Tz::load("America/New_York").unwrap();
+///
+/// // 02:30 is skipped as the clocks are turned forward at 2018-03-11T02:00:00-05:00
+/// let wall = "2018-03-11T02:30:00".parse().unwrap();
+/// assert_eq!(
+///     tz.resolve(&wall, Disambiguation::Compatible),
+///     parse_rfc3339("2018-03-11T03:30:00-04:00").ok()
+/// );
+/// assert_eq!(tz.resolve(&wall, Disambiguation::Reject), None);
+///
+/// let t = parse_rfc3339("2018-07-01T12:00:00Z").unwrap();
+/// assert_eq!(tz.offset_at(&t).offset(), -4 * 3600);
+/// assert_eq!(tz.abbreviation_at(&t), "EDT");
+/// ```
+///
+/// [`IANA time zone database`]: https://www.iana.org/time-zones
+/// [`TZif`]: https://tools.ietf.org/html/rfc8536
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Tz {
+    name: String,
+    // transition times in seconds since 1970-01-01T00:00:00Z, ascending
+    transitions: Vec<i64>,
+    // indices into types of the local time types taking effect at the transitions
+    indices: Vec<usize>,
+    types: Vec<LocalType>,
+    // the rule after the last transition
+    footer: Option<PosixTz>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct LocalType {
+    timezone: TimeZone,
+    dst: bool,
+    abbreviation: String,
+}
+
+impl Tz {
+    /// Loads the named zone from the zoneinfo directory in the environment variable `TZDIR`,
+    /// or the first of "/usr/share/zoneinfo", "/usr/lib/zoneinfo" and "/usr/share/lib/zoneinfo"
+    /// containing it.
+    ///
+    /// Returns an error of kind `InvalidInput` for a name escaping the directory,
+    /// and `InvalidData` for a malformed file.
+    pub fn load(name: &str) -> io::Result<Tz> {
+        let path = Path::new(name);
+        let relative =
+            !name.is_empty() && path.components().all(|c| matches!(c, Component::Normal(_)));
+        if !relative {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid time zone name",
+            ));
+        }
+
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        if let Some(dir) = env::var_os("TZDIR") {
+            dirs.push(dir.into());
+        }
+        dirs.extend(ZONEINFO_DIRS.iter().map(PathBuf::from));
+
+        let mut err = io::Error::new(io::ErrorKind::NotFound, "time zone not found");
+        for dir in dirs {
+            match fs::read(dir.join(path)) {
+                Ok(data) => return Tz::from_file_data(name, &data),
+                Err(e) => {
+                    if e.kind() != io::ErrorKind::NotFound {
+                        err = e;
+                    }
+                }
+            }
+        }
+
+        Err(err)
+    }
+
+    /// Loads a zone from a TZif file, like the "/etc/localtime" of most systems
+    pub fn load_file<P: AsRef<Path>>(name: &str, path: P) -> io::Result<Tz> {
+        Tz::from_file_data(name, &fs::read(path)?)
+    }
+
+    /// Returns the zone of the given TZif data, which may be embedded with `include_bytes!`
+    ///
+    /// Returns `ParseError::TooShort` for truncated data, and `ParseError::Malformed`
+    /// for a bad header or inconsistent data.
+    pub fn from_tzif(name: &str, data: &[u8]) -> Result<Tz, ParseError> {
+        let mut r = Reader { data };
+        let header = read_header(&mut r)?;
+
+        // skips the 32-bit data of version 1 for the 64-bit data of later versions
+        let (header, time_size) = if header.version >= b'2' {
+            r.take(header.data_length(4)?)?;
+            (read_header(&mut r)?, 8)
+        } else {
+            (header, 4)
+        };
+
+        // checks the length before allocating by the counts
+        if r.data.len() < header.data_length(time_size)? {
+            return Err(ParseError::TooShort);
+        }
+
+        let mut transitions = Vec::with_capacity(header.timecnt);
+        for _ in 0..header.timecnt {
+            let t = if time_size == 8 {
+                r.i64()?
+            } else {
+                i64::from(r.i32()?)
+            };
+            if transitions.last().is_some_and(|&last| last >= t) {
+                return Err(ParseError::Malformed);
+            }
+            transitions.push(t);
+        }
+
+        let mut indices = Vec::with_capacity(header.timecnt);
+        for _ in 0..header.timecnt {
+            let i = r.take(1)?[0] as usize;
+            if i >= header.typecnt {
+                return Err(ParseError::Malformed);
+            }
+            indices.push(i);
+        }
+
+        let mut records = Vec::with_capacity(header.typecnt);
+        for _ in 0..header.typecnt {
+            let offset = r.i32()?;
+            let flags = r.take(2)?;
+            records.push((offset, flags[0], flags[1] as usize));
+        }
+
+        let chars = r.take(header.charcnt)?;
+        r.take(header.leapcnt * (time_size + 4) + header.isstdcnt + header.isutcnt)?;
+
+        let footer = if time_size == 8 {
+            read_footer(&mut r)?
+        } else {
+            None
+        };
+
+        let mut types = Vec::with_capacity(header.typecnt);
+        for (offset, dst, index) in records {
+            if dst > 1 || index >= chars.len() {
+                return Err(ParseError::Malformed);
+            }
+
+            let abbreviation = &chars[index..];
+            let end = abbreviation
+                .iter()
+                .position(|&c| c == 0)
+                .ok_or(ParseError::Malformed)?;
+            let abbreviation =
+                String::from_utf8(abbreviation[..end].to_vec()).or(Err(ParseError::Malformed))?;
+
+            types.push(LocalType {
+                timezone: TimeZone::from_offset(offset).ok_or(ParseError::Malformed)?,
+                dst: dst == 1,
+                abbreviation,
+            });
+        }
+
+        Ok(Tz {
+            name: name.to_owned(),
+            transitions,
+            indices,
+            types,
+            footer,
+        })
+    }
+
+    /// Returns the name of the zone
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the abbreviation of the local time at the time, (e.g. "EST", "EDT")
+    pub fn abbreviation_at(&self, t: &Time) -> &str {
+        match self.footer_at(t) {
+            Some(footer) => footer.abbreviation_at(t),
+            None => &self.local_type(t).abbreviation,
+        }
+    }
+
+    /// Returns whether daylight saving time is in effect at the time
+    pub fn is_dst_at(&self, t: &Time) -> bool {
+        match self.footer_at(t) {
+            Some(footer) => footer.is_dst_at(t),
+            None => self.local_type(t).dst,
+        }
+    }
+
+    fn footer_at(&self, t: &Time) -> Option<&PosixTz> {
+        let footer = self.footer.as_ref()?;
+        if self
+            .transitions
+            .last()
+            .is_some_and(|&last| t.timestamp() < last)
+        {
+            return None;
+        }
+
+        Some(footer)
+    }
+
+    fn local_type(&self, t: &Time) -> &LocalType {
+        let ts = t.timestamp();
+        let i = match self.transitions.partition_point(|&x| x <= ts) {
+            // the first local time type is used before the first transition
+            0 => 0,
+            n => self.indices[n - 1],
+        };
+
+        &self.types[i]
+    }
+
+    fn from_file_data(name: &str, data: &[u8]) -> io::Result<Tz> {
+        Tz::from_tzif(name, data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl Zone for Tz {
+    fn offset_at(&self, t: &Time) -> TimeZone {
+        match self.footer_at(t) {
+            Some(footer) => footer.offset_at(t),
+            None => self.local_type(t).timezone,
+        }
+    }
+}
+
+struct Header {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl Header {
+    // returns the length of the data block following the header, the counts are untrusted,
+    // so an overflowing length is TooShort as no data can be that long
+    fn data_length(&self, time_size: usize) -> Result<usize, ParseError> {
+        let length = self
+            .timecnt
+            .checked_mul(time_size + 1)
+            .and_then(|n| n.checked_add(self.typecnt.checked_mul(6)?))
+            .and_then(|n| n.checked_add(self.charcnt))
+            .and_then(|n| n.checked_add(self.leapcnt.checked_mul(time_size + 4)?))
+            .and_then(|n| n.checked_add(self.isstdcnt))
+            .and_then(|n| n.checked_add(self.isutcnt));
+
+        length.ok_or(ParseError::TooShort)
+    }
+}
+
+fn read_header(r: &mut Reader) -> Result<Header, ParseError> {
+    let bs = r.take(TZIF_HEADER_LENGTH)?;
+    if !bs.starts_with(TZIF_MAGIC) {
+        return Err(ParseError::Malformed);
+    }
+
+    let version = bs[4];
+    if version != 0 && version < b'2' {
+        return Err(ParseError::Malformed);
+    }
+
+    let mut counts = Reader { data: &bs[20..] };
+    let header = Header {
+        version,
+        isutcnt: counts.u32()? as usize,
+        isstdcnt: counts.u32()? as usize,
+        leapcnt: counts.u32()? as usize,
+        timecnt: counts.u32()? as usize,
+        typecnt: counts.u32()? as usize,
+        charcnt: counts.u32()? as usize,
+    };
+
+    if header.typecnt == 0
+        || (header.isutcnt != 0 && header.isutcnt != header.typecnt)
+        || (header.isstdcnt != 0 && header.isstdcnt != header.typecnt)
+    {
+        return Err(ParseError::Malformed);
+    }
+
+    Ok(header)
+}
+
+// reads the POSIX TZ string between newlines after the data of version 2 and later,
+// an empty one means no rule
+fn read_footer(r: &mut Reader) -> Result<Option<PosixTz>, ParseError> {
+    if r.data.is_empty() {
+        return Ok(None);
+    }
+
+    if r.take(1)? != b"\n" {
+        return Err(ParseError::Malformed);
+    }
+
+    let end = r
+        .data
+        .iter()
+        .position(|&c| c == b'\n')
+        .ok_or(ParseError::Malformed)?;
+    let footer = r.take(end + 1)?;
+    if !r.data.is_empty() {
+        return Err(ParseError::Malformed);
+    }
+
+    match &footer[..end] {
+        [] => Ok(None),
+        s => {
+            let s = str::from_utf8(s).or(Err(ParseError::Malformed))?;
+            PosixTz::new(s).map(Some).or(Err(ParseError::Malformed))
+        }
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ParseError> {
+        if self.data.len() < n {
+            return Err(ParseError::TooShort);
+        }
+
+        let (bs, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(bs)
+    }
+
+    fn u32(&mut self) -> Result<u32, ParseError> {
+        let bs = self.take(4)?;
+        Ok(u32::from_be_bytes([bs[0], bs[1], bs[2], bs[3]]))
+    }
+
+    fn i32(&mut self) -> Result<i32, ParseError> {
+        self.u32().map(|n| n as i32)
+    }
+
+    fn i64(&mut self) -> Result<i64, ParseError> {
+        let bs = self.take(8)?;
+        let mut buf = [0; 8];
+        buf.copy_from_slice(bs);
+        Ok(i64::from_be_bytes(buf))
+    }
+}
@@ -0,0 +1,128 @@
+use super::{Time, UNIX_EPOCH};
+use duration::Unit;
+use std::time::Duration;
+use ParseError;
+
+// the largest integer parts detected as seconds, milliseconds and microseconds
+const AUTO_SECONDS_MAX: u64 = 99_999_999_999;
+const AUTO_MILLIS_MAX: u64 = 99_999_999_999_999;
+const AUTO_MICROS_MAX: u64 = 99_999_999_999_999_999;
+
+/// Parses a Unix timestamp, (e.g. "1537520204", "1537520204.234", "-1.5", "@1537520204"),
+/// counted in `unit` since 1970-01-01T00:00:00Z.
+///
+/// If `unit` is None, it is detected by the magnitude of the integer part:
+/// up to 11 digits are seconds, up to 14 milliseconds, up to 17 microseconds,
+/// and longer ones nanoseconds. Digits of the fraction beyond nanoseconds are truncated.
+///
+/// # Example
+/// ```
+/// use humanize_rs::duration::Unit;
+/// use humanize_rs::time::{parse_rfc3339, parse_timestamp};
+///
+/// let t = parse_rfc3339("2018-09-21T08:56:44.234Z");
+/// assert_eq!(parse_timestamp("1537520204.234", None), t);
+/// assert_eq!(parse_timestamp("1537520204234", None), t);
+/// assert_eq!(parse_timestamp("@1537520204234000", None), t);
+/// assert_eq!(parse_timestamp("1537520204234", Some(Unit::Millisecond)), t);
+/// ```
+pub fn parse_timestamp(s: &str, unit: Option<Unit>) -> Result<Time, ParseError> {
+    let mut s = s.trim();
+    if s.is_empty() {
+        return Err(ParseError::EmptyInput);
+    }
+
+    s = s.strip_prefix('@').unwrap_or(s);
+
+    let neg = s.starts_with('-');
+    if neg || s.starts_with('+') {
+        s = &s[1..];
+    }
+
+    let (int, frac) = match s.find('.') {
+        Some(i) => (&s[..i], Some(&s[i + 1..])),
+        None => (s, None),
+    };
+
+    let digits = |s: &str| s.bytes().all(|c| c.is_ascii_digit());
+    if !digits(int) || !frac.is_none_or(digits) {
+        return Err(ParseError::InvalidValue);
+    }
+
+    let frac = frac.unwrap_or("");
+    if int.is_empty() && frac.is_empty() {
+        return Err(ParseError::MissingValue);
+    }
+
+    let n = match int {
+        "" => 0,
+        _ => int.parse::<u64>().or(Err(ParseError::Overflow))?,
+    };
+
+    let unit = unit.unwrap_or(match n {
+        n if n <= AUTO_SECONDS_MAX => Unit::Second,
+        n if n <= AUTO_MILLIS_MAX => Unit::Millisecond,
+        n if n <= AUTO_MICROS_MAX => Unit::Microsecond,
+        _ => Unit::Nanosecond,
+    });
+
+    let mut d = unit.times(n).ok_or(ParseError::Overflow)?;
+
+    // the fraction of one unit, which is at most one day
+    let frac = &frac[..frac.len().min(9)];
+    if !frac.is_empty() {
+        let f = frac.parse::<u32>().or(Err(ParseError::InvalidValue))?;
+        let f = unit.duration() * f / 10_u32.pow(frac.len() as u32);
+        d = d.checked_add(f).ok_or(ParseError::Overflow)?;
+    }
+
+    let t = if neg {
+        UNIX_EPOCH.checked_sub(d)
+    } else {
+        UNIX_EPOCH.checked_add(d)
+    };
+
+    t.ok_or(ParseError::Overflow)
+}
+
+impl Time {
+    /// Returns the whole seconds since 1970-01-01T00:00:00Z, negative before it
+    pub fn timestamp(&self) -> i64 {
+        self.sec - UNIX_EPOCH.sec
+    }
+
+    /// Returns the whole milliseconds since 1970-01-01T00:00:00Z, negative before it
+    pub fn timestamp_millis(&self) -> i64 {
+        self.timestamp() * 1000 + i64::from(self.nano / 1_000_000)
+    }
+
+    /// Returns the whole microseconds since 1970-01-01T00:00:00Z, negative before it
+    pub fn timestamp_micros(&self) -> i64 {
+        self.timestamp() * 1_000_000 + i64::from(self.nano / 1000)
+    }
+
+    /// Returns the nanoseconds since 1970-01-01T00:00:00Z, negative before it,
+    /// or None if it overflows i64, which covers the years 1677 to 2262
+    pub fn timestamp_nanos(&self) -> Option<i64> {
+        self.timestamp()
+            .checked_mul(1_000_000_000)?
+            .checked_add(i64::from(self.nano))
+    }
+
+    /// Returns the time of the given seconds and nanoseconds since 1970-01-01T00:00:00Z,
+    /// or None if it is out of range
+    pub fn from_timestamp(secs: i64, nano: u32) -> Option<Time> {
+        if nano >= 1_000_000_000 {
+            return None;
+        }
+
+        let d = Duration::from_secs(secs.unsigned_abs());
+        let t = if secs < 0 {
+            UNIX_EPOCH.checked_sub(d)
+        } else {
+            UNIX_EPOCH.checked_add(d)
+        };
+
+        t?.checked_add(Duration::new(0, nano))
+    }
+}
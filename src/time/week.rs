@@ -0,0 +1,208 @@
+use super::calendar::Weekday;
+use super::date::write_year;
+use super::{days_from_civil, read_expanded_year, read_u32, Date, Time, Zone};
+use std::fmt::{self, Write};
+use ParseError;
+
+/// Represents a week of the ISO 8601 week-numbering year, (e.g. "2018-W38"),
+/// weeks start on Monday and the first week of a year contains its first Thursday
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct IsoWeek {
+    year: i32,
+    week: u32,
+}
+
+impl IsoWeek {
+    /// Returns the week-numbering year, which differs from the calendar year
+    /// for a few days around the new year
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Returns the week, from 1 to 52 or 53
+    pub fn week(&self) -> u32 {
+        self.week
+    }
+}
+
+impl fmt::Display for IsoWeek {
+    /// Formats the week as "YYYY-Www"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_year(f, self.year)?;
+        write!(f, "-W{:02}", self.week)
+    }
+}
+
+impl Date {
+    /// Returns the date of the day of week in the week of the ISO 8601 week-numbering year,
+    /// or None if the week is out of the year
+    pub fn from_iso_week(year: i32, week: u32, weekday: Weekday) -> Option<Date> {
+        if week < 1 || week > weeks_in_year(year) {
+            return None;
+        }
+
+        // January 4 is always in the first week
+        let jan4 = Date::new(year, 1, 4)?;
+        let days = (week as i64 - 1) * 7 + weekday.iso_number() as i64
+            - jan4.weekday().iso_number() as i64;
+
+        jan4.checked_add_days(days)
+    }
+
+    /// Returns the date of the day of year, from 1 to 365 or 366, or None if it is out of the year
+    pub fn from_ordinal(year: i32, ordinal: u32) -> Option<Date> {
+        let jan1 = Date::new(year, 1, 1)?;
+        let days_in_year = if jan1.is_leap_year() { 366 } else { 365 };
+        if ordinal < 1 || ordinal > days_in_year {
+            return None;
+        }
+
+        jan1.checked_add_days(ordinal as i64 - 1)
+    }
+
+    /// Returns the week of the ISO 8601 week-numbering year
+    pub fn iso_week(&self) -> IsoWeek {
+        let week = (self.ordinal() + 10 - self.weekday().iso_number()) / 7;
+        match week {
+            0 => IsoWeek {
+                year: self.year() - 1,
+                week: weeks_in_year(self.year() - 1),
+            },
+            w if w > weeks_in_year(self.year()) => IsoWeek {
+                year: self.year() + 1,
+                week: 1,
+            },
+            w => IsoWeek {
+                year: self.year(),
+                week: w,
+            },
+        }
+    }
+
+    /// Returns the day of year, from 1 to 365 or 366
+    pub fn ordinal(&self) -> u32 {
+        (self.days() - days_from_civil(self.year() as i64, 1, 1)) as u32 + 1
+    }
+}
+
+impl Time {
+    /// Returns the week of the ISO 8601 week-numbering year of the time in the zone
+    pub fn iso_week<Z: Zone>(&self, zone: Z) -> IsoWeek {
+        self.to_date(zone).iso_week()
+    }
+
+    /// Returns the day of week of the time in the zone
+    pub fn weekday<Z: Zone>(&self, zone: Z) -> Weekday {
+        self.to_date(zone).weekday()
+    }
+
+    /// Returns the day of year of the time in the zone, from 1 to 365 or 366
+    pub fn ordinal<Z: Zone>(&self, zone: Z) -> u32 {
+        self.to_date(zone).ordinal()
+    }
+}
+
+/// Parses an ISO 8601 week date, "YYYY-Www-D" or "YYYYWwwD", (e.g. "2018-W38-5"),
+/// where D is the day of week from 1 for Monday to 7 for Sunday,
+/// the year may be expanded with a sign and up to 6 digits, (e.g. "-0044-W11-4")
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{format_week_date, parse_week_date, Date};
+///
+/// let date = parse_week_date("2018-W38-5").unwrap();
+/// assert_eq!(date, Date::new(2018, 9, 21).unwrap());
+/// assert_eq!(parse_week_date("2018W385"), Ok(date));
+/// assert_eq!(format_week_date(&date), "2018-W38-5");
+///
+/// // the week-numbering year may differ from the calendar year
+/// assert_eq!(parse_week_date("2020-W01-1"), Ok(Date::new(2019, 12, 30).unwrap()));
+/// ```
+pub fn parse_week_date(s: &str) -> Result<Date, ParseError> {
+    let bs = s.trim().as_bytes();
+    if let Some((year, n)) = read_expanded_year(bs)? {
+        let bs = &bs[n..];
+        if bs.len() != 6 || bs[1] != b'W' || bs[4] != b'-' {
+            return Err(ParseError::Malformed);
+        }
+
+        return week_date(year as i32, &bs[2..4], &bs[5..6]);
+    }
+
+    let (year, week, day) = match bs.len() {
+        0 => return Err(ParseError::EmptyInput),
+        10 if bs[4] == b'-' && bs[5] == b'W' && bs[8] == b'-' => (&bs[0..4], &bs[6..8], &bs[9..10]),
+        8 if bs[4] == b'W' => (&bs[0..4], &bs[5..7], &bs[7..8]),
+        n if n < 8 => return Err(ParseError::TooShort),
+        n if n > 10 => return Err(ParseError::TooLong),
+        _ => return Err(ParseError::Malformed),
+    };
+
+    week_date(read_u32(year)? as i32, week, day)
+}
+
+/// Formats the date as an ISO 8601 week date, "YYYY-Www-D", (e.g. "2018-W38-5")
+pub fn format_week_date(date: &Date) -> String {
+    format!("{}-{}", date.iso_week(), date.weekday().iso_number())
+}
+
+/// Parses an ISO 8601 ordinal date, "YYYY-DDD" or "YYYYDDD", (e.g. "2018-264"),
+/// where DDD is the day of year from 001,
+/// the year may be expanded with a sign and up to 6 digits, (e.g. "+12345-074")
+///
+/// # Example
+/// ```
+/// use humanize_rs::time::{format_ordinal_date, parse_ordinal_date, Date};
+///
+/// let date = parse_ordinal_date("2018-264").unwrap();
+/// assert_eq!(date, Date::new(2018, 9, 21).unwrap());
+/// assert_eq!(parse_ordinal_date("2018264"), Ok(date));
+/// assert_eq!(format_ordinal_date(&date), "2018-264");
+/// ```
+pub fn parse_ordinal_date(s: &str) -> Result<Date, ParseError> {
+    let bs = s.trim().as_bytes();
+    if let Some((year, n)) = read_expanded_year(bs)? {
+        let bs = &bs[n..];
+        if bs.len() != 4 {
+            return Err(ParseError::Malformed);
+        }
+
+        return Date::from_ordinal(year as i32, read_u32(&bs[1..])?).ok_or(ParseError::Overflow);
+    }
+
+    let (year, day) = match bs.len() {
+        0 => return Err(ParseError::EmptyInput),
+        8 if bs[4] == b'-' => (&bs[0..4], &bs[5..8]),
+        7 => (&bs[0..4], &bs[4..7]),
+        n if n < 7 => return Err(ParseError::TooShort),
+        n if n > 8 => return Err(ParseError::TooLong),
+        _ => return Err(ParseError::Malformed),
+    };
+
+    let year = read_u32(year)? as i32;
+    Date::from_ordinal(year, read_u32(day)?).ok_or(ParseError::Overflow)
+}
+
+/// Formats the date as an ISO 8601 ordinal date, "YYYY-DDD", (e.g. "2018-264")
+pub fn format_ordinal_date(date: &Date) -> String {
+    let mut s = String::new();
+    let _ = write_year(&mut s, date.year());
+    let _ = write!(s, "-{:03}", date.ordinal());
+    s
+}
+
+// returns the date of the week "ww" and the day of week "D" in the week-numbering year
+fn week_date(year: i32, week: &[u8], day: &[u8]) -> Result<Date, ParseError> {
+    let week = read_u32(week)?;
+    let weekday = Weekday::from_iso_number(read_u32(day)?).ok_or(ParseError::Overflow)?;
+
+    Date::from_iso_week(year, week, weekday).ok_or(ParseError::Overflow)
+}
+
+// returns the number of weeks in the ISO 8601 week-numbering year, 52 or 53
+fn weeks_in_year(year: i32) -> u32 {
+    // December 28 is always in the last week
+    let dec28 = days_from_civil(year as i64, 12, 28);
+    let ordinal = (dec28 - days_from_civil(year as i64, 1, 1)) as u32 + 1;
+    (ordinal + 10 - Weekday::from_days(dec28).iso_number()) / 7
+}
@@ -0,0 +1,255 @@
+use super::calendar::Weekday;
+use super::{civil_from_days, Time, TimeZone, SECS_PER_DAY};
+
+// the distance of the probes around a wall clock time, transitions are assumed to be farther apart
+const PROBE_SECONDS: i64 = SECS_PER_DAY as i64;
+
+/// A set of rules giving the offset from UTC at any time, like a region observing daylight saving time
+///
+/// A fixed `TimeZone` is a zone, and so is a reference to one. Rounding, calendar arithmetic,
+/// formats, log timestamps and relative phrases take any zone, their days and months start
+/// at the local midnight of the zone, which is resolved by [`Zone::resolve`]
+/// with `Disambiguation::Compatible` if it is skipped or repeated.
+///
+/// # Example
+/// ```
+/// use humanize_rs::duration::Unit;
+/// use humanize_rs::time::{parse_rfc3339, PosixTz};
+///
+/// let tz = "EST5EDT,M3.2.0,M11.1.0".parse::<PosixTz>().unwrap();
+/// // the clocks are turned forward at 2am, the day is 23 hours long
+/// let t = parse_rfc3339("2018-03-11T12:00:00-04:00").unwrap();
+/// assert_eq!(
+///     t.truncate(Unit::Day, &tz),
+///     parse_rfc3339("2018-03-11T00:00:00-05:00").ok()
+/// );
+/// assert_eq!(
+///     t.checked_add_days(1, &tz),
+///     parse_rfc3339("2018-03-12T12:00:00-04:00").ok()
+/// );
+/// ```
+///
+/// [`Zone::resolve`]: #method.resolve
+pub trait Zone {
+    /// Returns the offset from UTC in effect at the time
+    fn offset_at(&self, t: &Time) -> TimeZone;
+
+    /// Returns the calendar components of the time in this zone
+    fn components(&self, t: &Time) -> Components {
+        t.components(self.offset_at(t))
+    }
+
+    /// Returns the times showing the given wall clock time in this zone,
+    /// `wall` is the wall clock time read as UTC, (e.g. parsed from "2018-03-11T02:30:00")
+    fn local(&self, wall: &Time) -> LocalResult {
+        let local = wall.sec;
+        let probe = |delta: i64| match Time::from_local(local + delta, 0, TimeZone::utc()) {
+            Some(t) => self.offset_at(&t),
+            None => self.offset_at(wall),
+        };
+
+        let before = probe(-PROBE_SECONDS);
+        let after = probe(PROBE_SECONDS);
+
+        let mut found: Vec<Time> = Vec::with_capacity(2);
+        for tz in [before, probe(0), after].iter() {
+            if let Some(t) = Time::from_local(local, wall.nano, *tz) {
+                if self.offset_at(&t) == *tz && !found.contains(&t) {
+                    found.push(t);
+                }
+            }
+        }
+
+        found.sort_by_key(|t| t.sec);
+        match found.len() {
+            0 => {}
+            1 => return LocalResult::Single(found.remove(0)),
+            _ => {
+                let later = found.pop().unwrap();
+                return LocalResult::Ambiguous(found.remove(0), later);
+            }
+        }
+
+        // in a gap, the clocks are turned forward from the offset before to the one after
+        match (
+            Time::from_local(local, wall.nano, after),
+            Time::from_local(local, wall.nano, before),
+        ) {
+            (Some(earlier), Some(later)) => LocalResult::Skipped(earlier, later),
+            _ => LocalResult::None,
+        }
+    }
+
+    /// Returns the time showing the given wall clock time in this zone,
+    /// choosing one by `disambiguation` if it is skipped or repeated.
+    /// Returns None if it is rejected or out of range.
+    fn resolve(&self, wall: &Time, disambiguation: Disambiguation) -> Option<Time> {
+        use self::Disambiguation::*;
+
+        match (self.local(wall), disambiguation) {
+            (LocalResult::Single(t), _) => Some(t),
+            (LocalResult::Ambiguous(t, _), Compatible)
+            | (LocalResult::Ambiguous(t, _), Earlier)
+            | (LocalResult::Ambiguous(_, t), Later)
+            | (LocalResult::Skipped(t, _), Earlier)
+            | (LocalResult::Skipped(_, t), Compatible)
+            | (LocalResult::Skipped(_, t), Later) => Some(t),
+            _ => None,
+        }
+    }
+}
+
+impl Zone for TimeZone {
+    fn offset_at(&self, _: &Time) -> TimeZone {
+        *self
+    }
+
+    fn local(&self, wall: &Time) -> LocalResult {
+        match Time::from_local(wall.sec, wall.nano, *self) {
+            Some(t) => LocalResult::Single(t),
+            None => LocalResult::None,
+        }
+    }
+}
+
+impl<Z: Zone + ?Sized> Zone for &Z {
+    fn offset_at(&self, t: &Time) -> TimeZone {
+        (**self).offset_at(t)
+    }
+
+    fn components(&self, t: &Time) -> Components {
+        (**self).components(t)
+    }
+
+    fn local(&self, wall: &Time) -> LocalResult {
+        (**self).local(wall)
+    }
+
+    fn resolve(&self, wall: &Time, disambiguation: Disambiguation) -> Option<Time> {
+        (**self).resolve(wall, disambiguation)
+    }
+}
+
+// returns the time showing the local seconds in the zone, keeping the offset `prefer`
+// if the zone shows it there, or resolving the wall clock time by `disambiguation` otherwise
+pub(crate) fn resolve_local<Z: Zone + ?Sized>(
+    zone: &Z,
+    local: i64,
+    nano: u32,
+    prefer: Option<TimeZone>,
+    disambiguation: Disambiguation,
+) -> Option<Time> {
+    if let Some(tz) = prefer {
+        if let Some(t) = Time::from_local(local, nano, tz) {
+            if zone.offset_at(&t) == tz {
+                return Some(t);
+            }
+        }
+    }
+
+    let wall = Time::from_local(local, nano, TimeZone::utc())?;
+    zone.resolve(&wall, disambiguation)
+}
+
+/// The times showing a wall clock time in a [`Zone`]
+///
+/// [`Zone`]: ./trait.Zone.html
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LocalResult {
+    /// The wall clock time is shown exactly once
+    Single(Time),
+
+    /// The wall clock time is shown twice as the clocks are turned back,
+    /// the earlier and the later time
+    Ambiguous(Time, Time),
+
+    /// The wall clock time is skipped as the clocks are turned forward,
+    /// the times read with the offset after and before the transition,
+    /// which are the earlier and the later one
+    Skipped(Time, Time),
+
+    /// The time is out of range
+    None,
+}
+
+/// Policy choosing a time for a wall clock time which is skipped or repeated in a [`Zone`]
+///
+/// [`Zone`]: ./trait.Zone.html
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Disambiguation {
+    /// The earlier time of a repeated one, and the later time of a skipped one,
+    /// which moves a skipped time forward by the length of the gap
+    #[default]
+    Compatible,
+
+    /// The earlier time
+    Earlier,
+
+    /// The later time
+    Later,
+
+    /// No time
+    Reject,
+}
+
+/// The calendar components of a time in a timezone
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Components {
+    /// The year of the proleptic Gregorian calendar, 0 is 1 BC and negative years are before it
+    pub year: i64,
+
+    /// The month, from 1 to 12
+    pub month: u32,
+
+    /// The day of month, from 1 to 31
+    pub day: u32,
+
+    /// The hour, from 0 to 23
+    pub hour: u32,
+
+    /// The minute, from 0 to 59
+    pub minute: u32,
+
+    /// The second, from 0 to 59
+    pub second: u32,
+
+    /// The nanosecond, from 0 to 999,999,999
+    pub nano: u32,
+
+    /// The day of week
+    pub weekday: Weekday,
+
+    /// The offset from UTC
+    pub timezone: TimeZone,
+}
+
+impl Time {
+    /// Returns the calendar components of the time in the given timezone
+    ///
+    /// # Example
+    /// ```
+    /// use humanize_rs::time::{parse_rfc3339, TimeZone, Weekday};
+    ///
+    /// let t = parse_rfc3339("2018-09-21T08:56:44Z").unwrap();
+    /// let c = t.components(TimeZone::new(8).unwrap());
+    /// assert_eq!((c.year, c.month, c.day), (2018, 9, 21));
+    /// assert_eq!((c.hour, c.minute, c.second), (16, 56, 44));
+    /// assert_eq!(c.weekday, Weekday::Friday);
+    /// ```
+    pub fn components(&self, timezone: TimeZone) -> Components {
+        let (days, secs) = self.local_days(timezone);
+        let (year, month, day) = civil_from_days(days);
+
+        Components {
+            year,
+            month,
+            day,
+            hour: secs / 3600,
+            minute: secs / 60 % 60,
+            second: secs % 60,
+            nano: self.nano,
+            weekday: Weekday::from_days(days),
+            timezone,
+        }
+    }
+}